@@ -0,0 +1,199 @@
+// https://oi-wiki.org/ds/dsu/
+
+pub struct DisjointSet {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    size: Vec<usize>,
+    count: usize,
+}
+
+impl DisjointSet {
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            rank: vec![0; n],
+            size: vec![1; n],
+            count: n,
+        }
+    }
+
+    pub fn find(&mut self, x: usize) -> usize {
+        let mut root = x;
+        while self.parent[root] != root {
+            root = self.parent[root];
+        }
+        // 路径压缩
+        let mut x = x;
+        while self.parent[x] != root {
+            let next = self.parent[x];
+            self.parent[x] = root;
+            x = next;
+        }
+        root
+    }
+
+    // 已在同一集合时返回 false
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let mut x = self.find(x);
+        let mut y = self.find(y);
+        if x == y {
+            return false;
+        }
+        if self.rank[x] < self.rank[y] {
+            std::mem::swap(&mut x, &mut y);
+        }
+        self.parent[y] = x;
+        self.size[x] += self.size[y];
+        if self.rank[x] == self.rank[y] {
+            self.rank[x] += 1;
+        }
+        self.count -= 1;
+        true
+    }
+
+    pub fn same(&mut self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    pub fn set_size(&mut self, x: usize) -> usize {
+        let root = self.find(x);
+        self.size[root]
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.count
+    }
+}
+
+// 不做路径压缩, 每次合并记录一条历史, 可按快照撤销
+// 配合线段树分治可用于离线动态连通性
+pub struct RollbackDisjointSet {
+    parent: Vec<usize>,
+    rank: Vec<u8>,
+    size: Vec<usize>,
+    count: usize,
+    history: Vec<(usize, bool)>, // 被挂到其他根下的根, 是否增加了秩
+}
+
+impl RollbackDisjointSet {
+    pub fn new(n: usize) -> Self {
+        Self {
+            parent: (0..n).collect(),
+            rank: vec![0; n],
+            size: vec![1; n],
+            count: n,
+            history: Vec::new(),
+        }
+    }
+
+    pub fn find(&self, x: usize) -> usize {
+        let mut x = x;
+        while self.parent[x] != x {
+            x = self.parent[x];
+        }
+        x
+    }
+
+    pub fn union(&mut self, x: usize, y: usize) -> bool {
+        let mut x = self.find(x);
+        let mut y = self.find(y);
+        if x == y {
+            return false;
+        }
+        if self.rank[x] < self.rank[y] {
+            std::mem::swap(&mut x, &mut y);
+        }
+        self.parent[y] = x;
+        self.size[x] += self.size[y];
+        let inc = self.rank[x] == self.rank[y];
+        if inc {
+            self.rank[x] += 1;
+        }
+        self.count -= 1;
+        self.history.push((y, inc));
+        true
+    }
+
+    pub fn same(&self, x: usize, y: usize) -> bool {
+        self.find(x) == self.find(y)
+    }
+
+    pub fn set_size(&self, x: usize) -> usize {
+        self.size[self.find(x)]
+    }
+
+    pub fn component_count(&self) -> usize {
+        self.count
+    }
+
+    pub fn snapshot(&self) -> usize {
+        self.history.len()
+    }
+
+    // 撤销最近一次成功的合并
+    pub fn undo(&mut self) -> bool {
+        let (y, inc) = match self.history.pop() {
+            Some(h) => h,
+            None => return false,
+        };
+        let x = self.parent[y];
+        self.parent[y] = y;
+        self.size[x] -= self.size[y];
+        if inc {
+            self.rank[x] -= 1;
+        }
+        self.count += 1;
+        true
+    }
+
+    pub fn rollback(&mut self, snapshot: usize) {
+        while self.history.len() > snapshot {
+            self.undo();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_disjoint_set() {
+        let mut s = DisjointSet::new(8);
+        assert_eq!(s.component_count(), 8);
+        assert!(s.union(0, 1));
+        assert!(s.union(2, 3));
+        assert!(s.union(1, 3));
+        assert!(!s.union(0, 2));
+        assert!(s.union(5, 6));
+        assert!(s.same(0, 3));
+        assert!(!s.same(0, 5));
+        assert_eq!(s.set_size(2), 4);
+        assert_eq!(s.set_size(6), 2);
+        assert_eq!(s.set_size(7), 1);
+        assert_eq!(s.component_count(), 4);
+    }
+
+    #[test]
+    fn test_rollback_disjoint_set() {
+        let mut s = RollbackDisjointSet::new(6);
+        s.union(0, 1);
+        let snap = s.snapshot();
+        s.union(1, 2);
+        s.union(3, 4);
+        assert!(!s.union(0, 2));
+        assert!(s.same(0, 2));
+        assert_eq!(s.set_size(0), 3);
+        assert_eq!(s.component_count(), 3);
+        assert!(s.undo());
+        assert!(!s.same(3, 4));
+        s.rollback(snap);
+        assert!(s.same(0, 1));
+        assert!(!s.same(0, 2));
+        assert_eq!(s.set_size(1), 2);
+        assert_eq!(s.component_count(), 5);
+        s.rollback(0);
+        assert!(!s.undo());
+        assert_eq!(s.component_count(), 6);
+    }
+}
@@ -2,12 +2,44 @@
 
 use std::collections::VecDeque;
 
+pub mod astar;
+pub mod bcc;
+pub mod closure;
+pub mod conn;
+pub mod cpm;
+pub mod csr;
+pub mod dom;
+pub mod dyntopo;
+pub mod euler;
+pub mod flow;
+pub mod io;
+pub mod labeled;
+pub mod matching;
+pub mod matrix;
+pub mod mcmf;
+pub mod mst;
+pub mod random;
 pub mod sc;
+pub mod sp;
 pub mod topo;
+pub mod tree;
+pub mod twosat;
 
+#[derive(Debug)]
 pub struct Graph {
     pub(self) vs: usize,
     pub(self) adj: Vec<Vec<usize>>,
+    in_deg: Vec<usize>, // 随加删边维护
+    removed: Vec<bool>, // 墓碑标记
+}
+
+// 删除顶点的方式
+// 墓碑顶点仍占用编号, 但不出现在 Neighbors::vertices 中, tarjan_scc, toposort_kahn 等算法
+// 及 to_dot, to_json 等转换都会跳过它
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Removal {
+    Compact,   // 删除后立即 compact, 连同之前的墓碑顶点一起回收并重新编号
+    Tombstone, // 只删去关联边并标记, 编号不变, 之后可用 compact 统一回收
 }
 
 impl Graph {
@@ -15,26 +47,124 @@ impl Graph {
         Self {
             vs,
             adj: vec![Vec::new(); vs],
+            in_deg: vec![0; vs],
+            removed: vec![false; vs],
         }
     }
 
+    pub fn add_vertex(&mut self) -> usize {
+        self.adj.push(Vec::new());
+        self.in_deg.push(0);
+        self.removed.push(false);
+        self.vs += 1;
+        self.vs - 1
+    }
+
     pub fn add_edge(&mut self, v: usize, w: usize) {
+        assert!(
+            !self.removed[v] && !self.removed[w],
+            "edge {} -> {} touches a removed vertex",
+            v,
+            w
+        );
         self.adj[v].push(w);
+        self.in_deg[w] += 1;
     }
 
-    pub fn in_degs(&self) -> Vec<usize> {
-        let mut in_degs = vec![0usize; self.vs];
+    pub fn has_edge(&self, v: usize, w: usize) -> bool {
+        self.adj[v].contains(&w)
+    }
+
+    // 有重边时只删去第一条, 不存在时返回 false
+    pub fn remove_edge(&mut self, v: usize, w: usize) -> bool {
+        match self.adj[v].iter().position(|&x| x == w) {
+            Some(i) => {
+                self.adj[v].remove(i);
+                self.in_deg[w] -= 1;
+                true
+            }
+            None => false,
+        }
+    }
+
+    // 去掉重边, 保留每条边第一次出现的位置, 返回删去的边数
+    pub fn dedup_edges(&mut self) -> usize {
+        let mut seen = vec![false; self.vs];
+        let mut removed = 0;
         for v in 0..self.vs {
-            for &w in self.adj[v].iter() {
-                in_degs[w] += 1;
+            let in_deg = &mut self.in_deg;
+            self.adj[v].retain(|&w| {
+                if seen[w] {
+                    in_deg[w] -= 1;
+                    removed += 1;
+                    return false;
+                }
+                seen[w] = true;
+                true
+            });
+            self.adj[v].iter().for_each(|&w| seen[w] = false);
+        }
+        removed
+    }
+
+    // Compact 方式返回旧编号到新编号的映射, 已删除的顶点为 None
+    // Tombstone 方式编号不变, 返回 None, 避免每次删除都构造 O(V) 的映射
+    pub fn remove_vertex(&mut self, v: usize, how: Removal) -> Option<Vec<Option<usize>>> {
+        for w in std::mem::take(&mut self.adj[v]) {
+            self.in_deg[w] -= 1;
+        }
+        for u in 0..self.vs {
+            self.adj[u].retain(|&w| w != v);
+        }
+        self.in_deg[v] = 0;
+        self.removed[v] = true;
+        match how {
+            Removal::Compact => Some(self.compact()),
+            Removal::Tombstone => None,
+        }
+    }
+
+    pub fn is_removed(&self, v: usize) -> bool {
+        self.removed[v]
+    }
+
+    // 回收所有墓碑顶点, 剩余顶点保持相对顺序; 返回旧编号到新编号的映射
+    pub fn compact(&mut self) -> Vec<Option<usize>> {
+        let mut map = vec![None; self.vs];
+        let mut n = 0;
+        for (v, id) in map.iter_mut().enumerate() {
+            if !self.removed[v] {
+                *id = Some(n);
+                n += 1;
             }
         }
-        in_degs
+        let mut adj = Vec::with_capacity(n);
+        let mut in_deg = Vec::with_capacity(n);
+        for v in 0..self.vs {
+            if !self.removed[v] {
+                let ws = std::mem::take(&mut self.adj[v]);
+                adj.push(ws.into_iter().map(|w| map[w].unwrap()).collect());
+                in_deg.push(self.in_deg[v]);
+            }
+        }
+        self.vs = n;
+        self.adj = adj;
+        self.in_deg = in_deg;
+        self.removed = vec![false; n];
+        map
+    }
+
+    pub fn in_deg(&self, v: usize) -> usize {
+        self.in_deg[v]
+    }
+
+    pub fn in_degs(&self) -> Vec<usize> {
+        self.in_deg.clone()
     }
 
     pub fn print(&self) {
         println!("graph: vs={}", self.vs);
-        for v in 0..self.vs {
+        for v in self.vertices() {
             println!("| adj vertex {}:", v);
             for &w in self.adj[v].iter() {
                 println!("- {} -> {}", v, w);
@@ -49,14 +179,173 @@ impl Graph {
                 g.add_edge(w, v);
             }
         }
+        g.removed = self.removed.clone();
+        g
+    }
+}
+
+pub struct WeightedGraph {
+    pub(self) vs: usize,
+    pub(self) adj: Vec<Vec<(usize, i64)>>,
+}
+
+impl WeightedGraph {
+    pub fn new(vs: usize) -> Self {
+        Self {
+            vs,
+            adj: vec![Vec::new(); vs],
+        }
+    }
+
+    pub fn add_edge(&mut self, v: usize, w: usize, weight: i64) {
+        self.adj[v].push((w, weight));
+    }
+}
+
+// 无向带权图, 每条边只存一次, adj 中记录关联边的编号
+pub struct WeightedUnGraph {
+    pub(self) vs: usize,
+    pub(self) edges: Vec<(usize, usize, i64)>,
+    pub(self) adj: Vec<Vec<usize>>,
+}
+
+impl WeightedUnGraph {
+    pub fn new(vs: usize) -> Self {
+        Self {
+            vs,
+            edges: Vec::new(),
+            adj: vec![Vec::new(); vs],
+        }
+    }
+
+    pub fn add_edge(&mut self, v: usize, w: usize, weight: i64) -> usize {
+        let e = self.edges.len();
+        self.edges.push((v, w, weight));
+        self.adj[v].push(e);
+        if v != w {
+            self.adj[w].push(e);
+        }
+        e
+    }
+
+    pub fn edges(&self) -> &[(usize, usize, i64)] {
+        &self.edges
+    }
+}
+
+// 无向图, 每条边在两端的邻接表中各存一次, 自环只存一次
+#[derive(Debug)]
+pub struct UnGraph {
+    pub(self) vs: usize,
+    pub(self) edges: usize,
+    pub(self) adj: Vec<Vec<usize>>,
+}
+
+impl UnGraph {
+    pub fn new(vs: usize) -> Self {
+        Self {
+            vs,
+            edges: 0,
+            adj: vec![Vec::new(); vs],
+        }
+    }
+
+    pub fn add_edge(&mut self, v: usize, w: usize) {
+        self.adj[v].push(w);
+        if v != w {
+            self.adj[w].push(v);
+        }
+        self.edges += 1;
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.edges
+    }
+
+    // 转为对称的有向图, 供 bcc 等以 Graph 为输入的算法使用
+    pub fn to_graph(&self) -> Graph {
+        let mut g = Graph::new(self.vs);
+        for v in 0..self.vs {
+            for &w in self.adj[v].iter() {
+                g.add_edge(v, w);
+            }
+        }
         g
     }
 }
 
-pub fn dfs_recur(g: &Graph, v: usize, visited: &mut [bool], f: &mut impl FnMut(usize)) {
+// 只读的邻接访问, 让 dfs, bfs, tarjan_scc, toposort_kahn 等可以跑在不同的存储上
+pub trait Neighbors {
+    fn vs(&self) -> usize;
+
+    fn neighbors(&self, v: usize) -> impl Iterator<Item = usize> + '_;
+
+    // 已删除的顶点没有关联边, 算法不应把它当作顶点输出
+    fn is_removed(&self, _v: usize) -> bool {
+        false
+    }
+
+    // 未删除的顶点, 升序
+    fn vertices(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.vs()).filter(move |&v| !self.is_removed(v))
+    }
+
+    // 有向图为出度, 无向图为邻居个数
+    fn degree(&self, v: usize) -> usize {
+        self.neighbors(v).count()
+    }
+
+    fn in_degs(&self) -> Vec<usize> {
+        let mut in_degs = vec![0usize; self.vs()];
+        for v in 0..self.vs() {
+            for w in self.neighbors(v) {
+                in_degs[w] += 1;
+            }
+        }
+        in_degs
+    }
+}
+
+impl Neighbors for Graph {
+    fn vs(&self) -> usize {
+        self.vs
+    }
+
+    fn neighbors(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        self.adj[v].iter().copied()
+    }
+
+    fn is_removed(&self, v: usize) -> bool {
+        self.removed[v]
+    }
+
+    fn degree(&self, v: usize) -> usize {
+        self.adj[v].len()
+    }
+
+    fn in_degs(&self) -> Vec<usize> {
+        Graph::in_degs(self)
+    }
+}
+
+impl Neighbors for UnGraph {
+    fn vs(&self) -> usize {
+        self.vs
+    }
+
+    fn neighbors(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        self.adj[v].iter().copied()
+    }
+
+    fn degree(&self, v: usize) -> usize {
+        self.adj[v].len()
+    }
+}
+
+pub fn dfs_recur<G: Neighbors>(g: &G, v: usize, visited: &mut [bool], f: &mut impl FnMut(usize)) {
     visited[v] = true;
     f(v);
-    for &w in g.adj[v].iter() {
+    for w in g.neighbors(v) {
         if visited[w] {
             continue;
         }
@@ -64,20 +353,43 @@ pub fn dfs_recur(g: &Graph, v: usize, visited: &mut [bool], f: &mut impl FnMut(u
     }
 }
 
-pub fn dfs(g: &Graph, v: usize, mut f: impl FnMut(usize)) {
-    let mut visited = vec![false; g.vs];
-    dfs_recur(g, v, &mut visited, &mut f);
+// 与 dfs_recur 访问顺序相同, 用显式栈代替递归, 不受线程栈大小限制
+pub fn dfs_iter<G: Neighbors>(g: &G, v: usize, visited: &mut [bool], f: &mut impl FnMut(usize)) {
+    visited[v] = true;
+    f(v);
+    let mut stack = vec![g.neighbors(v)]; // 每层尚未访问的邻居
+    while let Some(it) = stack.last_mut() {
+        match it.next() {
+            Some(w) => {
+                if !visited[w] {
+                    visited[w] = true;
+                    f(w);
+                    stack.push(g.neighbors(w));
+                }
+            }
+            None => {
+                stack.pop();
+            }
+        }
+    }
+}
+
+pub fn dfs<G: Neighbors>(g: &G, v: usize, mut f: impl FnMut(usize)) {
+    let mut visited = vec![false; g.vs()];
+    dfs_iter(g, v, &mut visited, &mut f);
 }
 
-pub fn bfs(g: &Graph, v: usize, mut f: impl FnMut(usize)) {
-    let mut visited = vec![false; g.vs];
+pub fn bfs<G: Neighbors>(g: &G, v: usize, mut f: impl FnMut(usize)) {
+    let mut visited = vec![false; g.vs()];
     let mut q = VecDeque::new();
+    // 入队时标记, 避免同一顶点多次入队
+    visited[v] = true;
     q.push_back(v);
     while let Some(v) = q.pop_front() {
-        visited[v] = true;
         f(v);
-        for &w in g.adj[v].iter() {
+        for w in g.neighbors(v) {
             if !visited[w] {
+                visited[w] = true;
                 q.push_back(w);
             }
         }
@@ -86,7 +398,7 @@ pub fn bfs(g: &Graph, v: usize, mut f: impl FnMut(usize)) {
 
 #[cfg(test)]
 mod tests {
-    use super::Graph;
+    use super::*;
 
     #[test]
     fn test_print() {
@@ -114,4 +426,118 @@ mod tests {
         g.print();
         g.transpose().print();
     }
+
+    #[test]
+    fn test_edit() {
+        let mut g = Graph::new(4);
+        g.add_edge(0, 1);
+        g.add_edge(0, 2);
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        g.add_edge(3, 2);
+        let v = g.add_vertex();
+        g.add_edge(v, 0);
+        g.add_edge(2, v);
+        assert!(g.has_edge(4, 0) && !g.has_edge(0, 4));
+        assert_eq!(g.in_degs(), vec![1, 2, 3, 0, 1]);
+        assert_eq!(g.dedup_edges(), 1);
+        assert_eq!(g.adj[0], vec![1, 2]);
+        assert!(g.remove_edge(1, 2));
+        assert!(!g.remove_edge(1, 2));
+        assert_eq!(g.in_deg(2), 2);
+
+        assert_eq!(g.remove_vertex(1, Removal::Tombstone), None);
+        assert!(g.is_removed(1));
+        assert_eq!(g.vs, 5);
+        assert_eq!(g.in_degs(), vec![1, 0, 2, 0, 1]);
+        // 之前的墓碑顶点 1 也一起回收
+        let map = g.remove_vertex(3, Removal::Compact);
+        assert_eq!(map, Some(vec![Some(0), None, Some(1), None, Some(2)]));
+        assert_eq!(g.vs, 3);
+        assert_eq!(g.adj, vec![vec![1], vec![2], vec![0]]);
+        assert_eq!(g.in_degs(), Neighbors::in_degs(&g.transpose().transpose()));
+        assert_eq!(g.compact(), vec![Some(0), Some(1), Some(2)]);
+    }
+
+    #[test]
+    fn test_tombstone_compact() {
+        use crate::graph::conn::connected_components;
+
+        let mut g = Graph::new(3);
+        g.add_edge(0, 2);
+        g.remove_vertex(1, Removal::Tombstone);
+        assert_eq!(g.vertices().collect::<Vec<_>>(), vec![0, 2]);
+        assert_eq!(topo::toposort_kahn(&g).unwrap(), vec![0, 2]);
+        assert_eq!(topo::toposort_dfs(&g).unwrap(), vec![0, 2]);
+        assert_eq!(topo::count_toposorts(&g), 1);
+        assert_eq!(sc::tarjan_scc(&g), vec![vec![2], vec![0]]);
+        assert_eq!(connected_components(&g), vec![vec![0, 2]]);
+        assert_eq!(csr::CsrGraph::from_graph(&g).vertices().count(), 2);
+        let s = io::to_edge_list(&g);
+        assert_eq!(s, "0 2\n");
+        assert!(io::parse_edge_list(&s).unwrap().is_removed(1));
+        assert!(io::from_json(&io::to_json(&g)).unwrap().is_removed(1));
+
+        assert_eq!(g.compact(), vec![Some(0), None, Some(1)]);
+        assert_eq!(topo::toposort_kahn(&g).unwrap(), vec![0, 1]);
+        assert_eq!(sc::tarjan_scc(&g), vec![vec![1], vec![0]]);
+        assert_eq!(connected_components(&g), vec![vec![0, 1]]);
+
+        let mut g = Graph::new(3);
+        for &(u, v) in [(0, 1), (1, 2), (2, 0), (0, 2)].iter() {
+            g.add_edge(u, v);
+        }
+        g.remove_vertex(1, Removal::Tombstone);
+        assert!(sc::is_sc(&g));
+        assert_eq!(sc::kosaraju_scc(&g).len(), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_edge_to_tombstone() {
+        let mut g = Graph::new(2);
+        g.remove_vertex(1, Removal::Tombstone);
+        g.add_edge(0, 1);
+    }
+
+    #[test]
+    fn test_dfs_iter() {
+        let mut g = Graph::new(6);
+        g.add_edge(0, 1);
+        g.add_edge(0, 4);
+        g.add_edge(1, 2);
+        g.add_edge(1, 3);
+        g.add_edge(1, 4);
+        g.add_edge(2, 3);
+        g.add_edge(3, 4);
+        g.add_edge(4, 1);
+        g.add_edge(5, 0);
+        for v in 0..6 {
+            let mut a = Vec::new();
+            let mut b = Vec::new();
+            dfs_recur(&g, v, &mut [false; 6], &mut |w| a.push(w));
+            dfs_iter(&g, v, &mut [false; 6], &mut |w| b.push(w));
+            assert_eq!(a, b);
+        }
+    }
+
+    #[test]
+    fn test_long_chain() {
+        let n = 1_000_000;
+        let mut g = Graph::new(n);
+        for v in 1..n {
+            g.add_edge(v - 1, v);
+        }
+        let mut cnt = 0;
+        dfs(&g, 0, |_| cnt += 1);
+        assert_eq!(cnt, n);
+        let scc = sc::tarjan_scc(&g);
+        assert_eq!(scc.len(), n);
+        assert_eq!(scc[0], vec![n - 1]);
+        let topo = topo::toposort_dfs(&g).unwrap();
+        assert!(topo.iter().enumerate().all(|(i, &v)| i == v));
+        g.add_edge(n - 1, 0);
+        assert_eq!(sc::tarjan_scc(&g).len(), 1);
+        assert!(topo::toposort_dfs(&g).is_err());
+    }
 }
@@ -0,0 +1,169 @@
+// https://oi-wiki.org/search/astar/
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+use std::hash::Hash;
+
+use super::*;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct AStarStats {
+    pub expanded: usize, // 出堆并扩展邻居的次数
+    pub pushed: usize,   // 入堆次数
+}
+
+// 邻居由 neighbors 按需生成, 适合网格等无需显式建图的场景
+// h 须为可采纳的估价函数 (不高估到 t 的距离), 非一致时顶点可能被重复扩展
+pub fn astar_implicit<N, I>(
+    s: N,
+    t: N,
+    mut neighbors: impl FnMut(N) -> I,
+    h: impl Fn(N) -> i64,
+) -> (Option<(i64, Vec<N>)>, AStarStats)
+where
+    N: Copy + Eq + Hash,
+    I: IntoIterator<Item = (N, i64)>,
+{
+    let mut stats = AStarStats::default();
+    let mut ids = HashMap::new();
+    let mut nodes = vec![s];
+    let mut dist = vec![0];
+    let mut pred = vec![None];
+    let mut heap = BinaryHeap::new();
+    ids.insert(s, 0);
+    heap.push(Reverse((h(s), 0, 0)));
+    stats.pushed += 1;
+    while let Some(Reverse((_, du, u))) = heap.pop() {
+        if du > dist[u] {
+            continue;
+        }
+        if nodes[u] == t {
+            let mut path = vec![t];
+            let mut w = u;
+            while let Some(p) = pred[w] {
+                path.push(nodes[p]);
+                w = p;
+            }
+            path.reverse();
+            return (Some((du, path)), stats);
+        }
+        stats.expanded += 1;
+        for (node, w) in neighbors(nodes[u]) {
+            let dv = du + w;
+            let v = match ids.get(&node) {
+                Some(&v) => {
+                    if dv >= dist[v] {
+                        continue;
+                    }
+                    dist[v] = dv;
+                    pred[v] = Some(u);
+                    v
+                }
+                None => {
+                    let v = nodes.len();
+                    ids.insert(node, v);
+                    nodes.push(node);
+                    dist.push(dv);
+                    pred.push(Some(u));
+                    v
+                }
+            };
+            heap.push(Reverse((dv + h(node), dv, v)));
+            stats.pushed += 1;
+        }
+    }
+    (None, stats)
+}
+
+pub fn astar(
+    g: &WeightedGraph,
+    s: usize,
+    t: usize,
+    h: impl Fn(usize) -> i64,
+) -> (Option<(i64, Vec<usize>)>, AStarStats) {
+    astar_implicit(s, t, |u| g.adj[u].iter().copied(), h)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::sp::dijkstra;
+
+    const GRID: [&str; 6] = [
+        "..#.....", "..#.##..", "....#...", ".####.#.", "......#.", "..#.....",
+    ];
+
+    fn grid_neighbors((x, y): (i64, i64)) -> Vec<((i64, i64), i64)> {
+        let mut ns = Vec::new();
+        for &(dx, dy) in [(0, 1), (1, 0), (0, -1), (-1, 0)].iter() {
+            let (nx, ny) = (x + dx, y + dy);
+            if nx < 0 || ny < 0 || nx >= 8 || ny >= 6 {
+                continue;
+            }
+            if GRID[ny as usize].as_bytes()[nx as usize] == b'#' {
+                continue;
+            }
+            // 纵向移动代价更高
+            ns.push(((nx, ny), if dx == 0 { 3 } else { 2 }));
+        }
+        ns
+    }
+
+    #[test]
+    fn test_astar_implicit() {
+        let mut g = WeightedGraph::new(48);
+        for y in 0..6 {
+            for x in 0..8 {
+                for ((nx, ny), w) in grid_neighbors((x, y)) {
+                    g.add_edge((y * 8 + x) as usize, (ny * 8 + nx) as usize, w);
+                }
+            }
+        }
+        let (dist, _) = dijkstra(&g, 0);
+        for y in 0..6 {
+            for x in 0..8 {
+                let t = (x, y);
+                let manhattan = |(px, py): (i64, i64)| (px - x).abs() * 2 + (py - y).abs() * 3;
+                let (r1, s1) = astar_implicit((0, 0), t, grid_neighbors, manhattan);
+                let (r2, s2) = astar_implicit((0, 0), t, grid_neighbors, |_| 0);
+                let d = dist[(y * 8 + x) as usize];
+                assert_eq!(r1.as_ref().map(|r| r.0), d);
+                assert_eq!(r2.as_ref().map(|r| r.0), d);
+                assert!(s1.expanded <= s2.expanded);
+                if let Some((d, path)) = r1 {
+                    assert_eq!(path.first(), Some(&(0, 0)));
+                    assert_eq!(path.last(), Some(&t));
+                    let len: i64 = path
+                        .windows(2)
+                        .map(|e| if e[0].0 == e[1].0 { 3 } else { 2 })
+                        .sum();
+                    assert_eq!(len, d);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn test_astar() {
+        let mut g = WeightedGraph::new(6);
+        g.add_edge(0, 1, 7);
+        g.add_edge(0, 2, 9);
+        g.add_edge(0, 5, 14);
+        g.add_edge(1, 2, 10);
+        g.add_edge(1, 3, 15);
+        g.add_edge(2, 3, 11);
+        g.add_edge(2, 5, 2);
+        g.add_edge(5, 4, 9);
+        g.add_edge(3, 4, 6);
+        let (dist, _) = dijkstra(&g, 0);
+        for (t, &d) in dist.iter().enumerate() {
+            let (r, stats) = astar(&g, 0, t, |_| 0);
+            assert_eq!(r.map(|r| r.0), d);
+            assert!(stats.expanded <= stats.pushed);
+        }
+        let (r, _) = astar(&g, 0, 4, |_| 0);
+        assert_eq!(r, Some((20, vec![0, 2, 5, 4])));
+        let (r, _) = astar(&g, 4, 0, |_| 0);
+        assert_eq!(r, None);
+    }
+}
@@ -0,0 +1,172 @@
+// https://oi-wiki.org/graph/cut/
+// https://oi-wiki.org/graph/bcc/
+// 以下算法都把 g 视为无向图, 每条无向边需要在两个方向上各加一次
+
+use super::sc::{tarjan_enter, tarjan_leave, TarjanContext};
+use super::*;
+
+// 无向图上的 tarjan, 顶点 u 回溯时调用 back(ctx, parent, u), 此时 low[u] 已确定
+// 只跳过一次通往父亲的边, 重边仍视为返祖边
+fn tarjan_undirected(
+    ctx: &mut TarjanContext,
+    root: usize,
+    back: &mut impl FnMut(&mut TarjanContext, Option<usize>, usize),
+) {
+    let mut call = vec![(root, None, 0, false)]; // 顶点, 父亲, 下一个邻居下标, 是否已跳过父边
+    tarjan_enter(ctx, root);
+    while let Some(top) = call.last_mut() {
+        let (u, p) = (top.0, top.1);
+        if top.2 < ctx.g.adj[u].len() {
+            let v = ctx.g.adj[u][top.2];
+            top.2 += 1;
+            if p == Some(v) && !top.3 {
+                top.3 = true;
+                continue;
+            }
+            if ctx.dfn[v] == 0 {
+                tarjan_enter(ctx, v);
+                call.push((v, Some(u), 0, false));
+            } else {
+                ctx.low[u] = ctx.low[u].min(ctx.dfn[v]);
+            }
+            continue;
+        }
+        call.pop();
+        if let Some(p) = p {
+            ctx.low[p] = ctx.low[p].min(ctx.low[u]);
+        }
+        back(ctx, p, u);
+    }
+}
+
+fn tarjan_undirected_all(
+    g: &Graph,
+    mut back: impl FnMut(&mut TarjanContext, Option<usize>, usize),
+) -> Vec<Vec<usize>> {
+    let mut ctx = TarjanContext::new(g);
+    for u in g.vertices() {
+        if ctx.dfn[u] == 0 {
+            tarjan_undirected(&mut ctx, u, &mut back);
+        }
+    }
+    ctx.scc
+}
+
+// 割点, 升序
+pub fn cut_vertices(g: &Graph) -> Vec<usize> {
+    let mut is_cut = vec![false; g.vs];
+    let mut children = vec![0; g.vs];
+    tarjan_undirected_all(g, |ctx, p, u| match p {
+        Some(p) => {
+            children[p] += 1;
+            if ctx.low[u] >= ctx.dfn[p] {
+                is_cut[p] = true;
+            }
+        }
+        // 根只有在 dfs 树上有两个以上儿子时才是割点
+        None => is_cut[u] = children[u] >= 2,
+    });
+    (0..g.vs).filter(|&u| is_cut[u]).collect()
+}
+
+// 桥, 每条以 (小端点, 大端点) 给出, 升序
+pub fn bridges(g: &Graph) -> Vec<(usize, usize)> {
+    let mut bridges = Vec::new();
+    tarjan_undirected_all(g, |ctx, p, u| {
+        if let Some(p) = p {
+            if ctx.low[u] > ctx.dfn[p] {
+                bridges.push((p.min(u), p.max(u)));
+            }
+        }
+    });
+    bridges.sort_unstable();
+    bridges
+}
+
+// 点双连通分量, 割点会出现在多个分量中, 孤立点单独成一个分量
+pub fn biconnected_components(g: &Graph) -> Vec<Vec<usize>> {
+    tarjan_undirected_all(g, |ctx, p, u| match p {
+        Some(p) => {
+            if ctx.low[u] >= ctx.dfn[p] {
+                let mut bcc = Vec::new();
+                while let Some(top) = ctx.stack.pop() {
+                    bcc.push(top);
+                    if top == u {
+                        break;
+                    }
+                }
+                bcc.push(p);
+                ctx.scc.push(bcc);
+            }
+        }
+        None => {
+            if ctx.stack.pop() == Some(u) && ctx.g.adj[u].iter().all(|&v| v == u) {
+                ctx.scc.push(vec![u]);
+            }
+        }
+    })
+}
+
+// 边双连通分量, 与有向图的强连通分量求法相同
+pub fn two_edge_connected_components(g: &Graph) -> Vec<Vec<usize>> {
+    tarjan_undirected_all(g, |ctx, _, u| tarjan_leave(ctx, u))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_g1() -> Graph {
+        let edges = [
+            (0, 1),
+            (1, 2),
+            (2, 0),
+            (1, 3),
+            (3, 4),
+            (4, 5),
+            (5, 3),
+            (5, 6),
+            (7, 8),
+            (8, 7),
+        ];
+        let mut g = Graph::new(10);
+        for &(u, v) in edges.iter() {
+            g.add_edge(u, v);
+            g.add_edge(v, u);
+        }
+        g
+    }
+
+    fn normalize(mut comps: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+        comps.iter_mut().for_each(|c| c.sort_unstable());
+        comps.sort();
+        comps
+    }
+
+    #[test]
+    fn test_cut_vertices() {
+        let g1 = get_g1();
+        assert_eq!(cut_vertices(&g1), vec![1, 3, 5]);
+        assert_eq!(bridges(&g1), vec![(1, 3), (5, 6)]);
+    }
+
+    #[test]
+    fn test_biconnected_components() {
+        let g1 = get_g1();
+        assert_eq!(
+            normalize(biconnected_components(&g1)),
+            vec![
+                vec![0, 1, 2],
+                vec![1, 3],
+                vec![3, 4, 5],
+                vec![5, 6],
+                vec![7, 8],
+                vec![9]
+            ]
+        );
+        assert_eq!(
+            normalize(two_edge_connected_components(&g1)),
+            vec![vec![0, 1, 2], vec![3, 4, 5], vec![6], vec![7, 8], vec![9]]
+        );
+    }
+}
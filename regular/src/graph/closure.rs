@@ -0,0 +1,147 @@
+// https://en.wikipedia.org/wiki/Transitive_closure
+// https://en.wikipedia.org/wiki/Transitive_reduction
+
+use super::sc::condensation;
+use super::topo::{toposort_kahn, Cycle};
+use super::*;
+
+// 同一强连通分量内的顶点可达集合相同, 缩点后按分量存位图
+pub struct TransitiveClosure {
+    comp: Vec<usize>,
+    members: Vec<Vec<usize>>,
+    words: usize,
+    rows: Vec<u64>, // 第 c 行为分量 c 可达的分量集合, 每行 words 个字
+}
+
+impl TransitiveClosure {
+    fn row(&self, c: usize) -> &[u64] {
+        &self.rows[c * self.words..(c + 1) * self.words]
+    }
+
+    // 自反, 每个顶点都可达自身
+    pub fn reachable(&self, u: usize, v: usize) -> bool {
+        let c = self.comp[v];
+        self.row(self.comp[u])[c / 64] & (1 << (c % 64)) != 0
+    }
+
+    // u 可达的全部顶点, 升序
+    pub fn reach(&self, u: usize) -> Vec<usize> {
+        let row = self.row(self.comp[u]);
+        let mut vs: Vec<usize> = (0..self.members.len())
+            .filter(|&c| row[c / 64] & (1 << (c % 64)) != 0)
+            .flat_map(|c| self.members[c].iter().copied())
+            .collect();
+        vs.sort_unstable();
+        vs
+    }
+}
+
+pub fn transitive_closure(g: &Graph) -> TransitiveClosure {
+    let cond = condensation(g);
+    let n = cond.members.len();
+    let words = n.div_ceil(64);
+    let mut rows = vec![0u64; n * words];
+    // 分量编号是拓扑序, 倒序处理时后继的行已经算好
+    for c in (0..n).rev() {
+        rows[c * words + c / 64] |= 1 << (c % 64);
+        for &d in cond.dag.adj[c].iter() {
+            for i in 0..words {
+                rows[c * words + i] |= rows[d * words + i];
+            }
+        }
+    }
+    TransitiveClosure {
+        comp: cond.comp,
+        members: cond.members,
+        words,
+        rows,
+    }
+}
+
+// 只对 DAG 有定义, 结果唯一; 边 u -> v 冗余当且仅当 u 的另一个后继可达 v
+pub fn transitive_reduction(g: &Graph) -> Result<Graph, Cycle> {
+    toposort_kahn(g)?;
+    let tc = transitive_closure(g);
+    let mut r = Graph::new(g.vs);
+    for u in 0..g.vs {
+        let mut succ = g.adj[u].clone();
+        succ.sort_unstable();
+        succ.dedup();
+        for &v in succ.iter() {
+            if !succ.iter().any(|&w| w != v && tc.reachable(w, v)) {
+                r.add_edge(u, v);
+            }
+        }
+    }
+    Ok(r)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 4 层, 每层 3 个顶点, 相邻层全连接, 另有跨层边
+    fn get_layered() -> Graph {
+        let mut g = Graph::new(12);
+        for l in 0..3 {
+            for i in 0..3 {
+                for j in 0..3 {
+                    g.add_edge(l * 3 + i, (l + 1) * 3 + j);
+                }
+            }
+        }
+        g.add_edge(0, 6);
+        g.add_edge(1, 11);
+        g.add_edge(5, 9);
+        g.add_edge(0, 3);
+        g
+    }
+
+    #[test]
+    fn test_transitive_closure() {
+        let g = get_layered();
+        let tc = transitive_closure(&g);
+        for u in 0..12 {
+            for v in 0..12 {
+                assert_eq!(tc.reachable(u, v), u == v || u / 3 < v / 3);
+            }
+        }
+        assert_eq!(tc.reach(7), vec![7, 9, 10, 11]);
+
+        let mut g2 = Graph::new(5);
+        g2.add_edge(0, 1);
+        g2.add_edge(1, 2);
+        g2.add_edge(2, 0);
+        g2.add_edge(2, 3);
+        let tc = transitive_closure(&g2);
+        assert!(tc.reachable(2, 1));
+        assert!(tc.reachable(1, 3));
+        assert!(!tc.reachable(3, 0));
+        assert!(!tc.reachable(0, 4));
+        assert_eq!(tc.reach(1), vec![0, 1, 2, 3]);
+    }
+
+    #[test]
+    fn test_transitive_reduction() {
+        let g = get_layered();
+        let r = transitive_reduction(&g).unwrap();
+        let mut edges = 0;
+        for u in 0..12 {
+            let mut succ = r.adj[u].clone();
+            succ.sort_unstable();
+            let expect: Vec<usize> = if u < 9 {
+                (u / 3 * 3 + 3..u / 3 * 3 + 6).collect()
+            } else {
+                Vec::new()
+            };
+            assert_eq!(succ, expect);
+            edges += succ.len();
+        }
+        assert_eq!(edges, 27);
+
+        let mut g2 = Graph::new(3);
+        g2.add_edge(0, 1);
+        g2.add_edge(1, 0);
+        assert!(transitive_reduction(&g2).is_err());
+    }
+}
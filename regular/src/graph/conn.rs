@@ -0,0 +1,143 @@
+// https://oi-wiki.org/graph/bi-graph/
+// 对任意 Neighbors 存储可用, 跳过已删除的顶点; 有向图上的连通分量为弱连通分量
+
+use std::collections::VecDeque;
+
+use super::*;
+use crate::dsu::DisjointSet;
+
+// 每个分量内顶点升序, 分量按最小顶点排序
+pub fn connected_components<G: Neighbors>(g: &G) -> Vec<Vec<usize>> {
+    let mut dsu = DisjointSet::new(g.vs());
+    for v in 0..g.vs() {
+        for w in g.neighbors(v) {
+            dsu.union(v, w);
+        }
+    }
+    let mut id = vec![usize::MAX; g.vs()];
+    let mut comps: Vec<Vec<usize>> = Vec::new();
+    for v in g.vertices() {
+        let root = dsu.find(v);
+        if id[root] == usize::MAX {
+            id[root] = comps.len();
+            comps.push(Vec::new());
+        }
+        comps[id[root]].push(v);
+    }
+    comps
+}
+
+pub fn is_connected<G: Neighbors>(g: &G) -> bool {
+    connected_components(g).len() <= 1
+}
+
+// 要求邻接对称 (无向图); 返回每个顶点所在的一侧, 存在奇环时返回 None
+pub fn bipartition<G: Neighbors>(g: &G) -> Option<Vec<bool>> {
+    let mut side = vec![None; g.vs()];
+    let mut q = VecDeque::new();
+    for s in 0..g.vs() {
+        if side[s].is_some() {
+            continue;
+        }
+        side[s] = Some(false);
+        q.push_back(s);
+        while let Some(v) = q.pop_front() {
+            let sv = side[v].unwrap();
+            for w in g.neighbors(v) {
+                match side[w] {
+                    None => {
+                        side[w] = Some(!sv);
+                        q.push_back(w);
+                    }
+                    Some(sw) if sw == sv => return None,
+                    _ => {}
+                }
+            }
+        }
+    }
+    Some(side.into_iter().map(Option::unwrap).collect())
+}
+
+#[derive(Debug, PartialEq)]
+pub struct DegreeStats {
+    pub min: usize,
+    pub max: usize,
+    pub mean: f64,
+    pub hist: Vec<usize>, // hist[d] 为度数为 d 的顶点数
+}
+
+// 度数取 Neighbors::degree, 即有向图的出度
+pub fn degree_stats<G: Neighbors>(g: &G) -> DegreeStats {
+    let degs: Vec<usize> = g.vertices().map(|v| g.degree(v)).collect();
+    let max = degs.iter().copied().max().unwrap_or(0);
+    let mut hist = vec![0; max + 1];
+    for &d in degs.iter() {
+        hist[d] += 1;
+    }
+    DegreeStats {
+        min: degs.iter().copied().min().unwrap_or(0),
+        max,
+        mean: if degs.is_empty() {
+            0.0
+        } else {
+            degs.iter().sum::<usize>() as f64 / degs.len() as f64
+        },
+        hist,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::matrix::MatrixGraph;
+
+    // 0-1-2-3-0 为偶环, 4-5 为一条边, 6 孤立
+    fn get_edges() -> Vec<(usize, usize)> {
+        vec![(0, 1), (1, 2), (2, 3), (3, 0), (4, 5)]
+    }
+
+    #[test]
+    fn test_components_and_bipartition() {
+        let mut g = UnGraph::new(7);
+        let mut m = MatrixGraph::new_undirected(7);
+        for &(v, w) in get_edges().iter() {
+            g.add_edge(v, w);
+            m.add_edge(v, w);
+        }
+        assert_eq!(g.edge_count(), 5);
+        assert_eq!(m.edge_count(), 5);
+        let expect = vec![vec![0, 1, 2, 3], vec![4, 5], vec![6]];
+        assert_eq!(connected_components(&g), expect);
+        assert_eq!(connected_components(&m), expect);
+        assert!(!is_connected(&g));
+
+        let sides = vec![false, true, false, true, false, true, false];
+        assert_eq!(bipartition(&g), Some(sides.clone()));
+        assert_eq!(bipartition(&m), Some(sides));
+        g.add_edge(0, 2);
+        m.add_edge(0, 2);
+        assert_eq!(bipartition(&g), None);
+        assert_eq!(bipartition(&m), None);
+
+        let mut d = Graph::new(3);
+        d.add_edge(0, 1);
+        d.add_edge(2, 1);
+        assert!(is_connected(&d));
+    }
+
+    #[test]
+    fn test_degree_stats() {
+        let mut g = UnGraph::new(7);
+        let mut m = MatrixGraph::new_undirected(7);
+        for &(v, w) in get_edges().iter() {
+            g.add_edge(v, w);
+            m.add_edge(v, w);
+        }
+        let s = degree_stats(&g);
+        assert_eq!(s, degree_stats(&m));
+        assert_eq!((s.min, s.max), (0, 2));
+        assert_eq!(s.hist, vec![1, 2, 4]);
+        assert!((s.mean - 10.0 / 7.0).abs() < 1e-9);
+        assert_eq!(degree_stats(&Graph::new(0)).hist, vec![0]);
+    }
+}
@@ -0,0 +1,82 @@
+// https://en.wikipedia.org/wiki/Critical_path_method
+// 顶点表示任务, dur[v] 为任务耗时, 边 u -> v 表示 v 必须在 u 完成后开始
+
+use super::topo::{toposort_kahn, Cycle};
+use super::*;
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct Schedule {
+    pub earliest: Vec<i64>, // 最早开始时间
+    pub latest: Vec<i64>,   // 不推迟总工期的最晚开始时间
+    pub slack: Vec<i64>,    // 可推迟的时间, 为 0 的任务在关键路径上
+    pub makespan: i64,      // 总工期, 即带权最长路的长度
+    pub critical_path: Vec<usize>,
+}
+
+pub fn critical_path(g: &Graph, dur: &[i64]) -> Result<Schedule, Cycle> {
+    assert_eq!(dur.len(), g.vs);
+    let topo = toposort_kahn(g)?;
+    let mut earliest = vec![0; g.vs];
+    for &u in topo.iter() {
+        for &v in g.adj[u].iter() {
+            earliest[v] = earliest[v].max(earliest[u] + dur[u]);
+        }
+    }
+    let makespan = (0..g.vs).map(|v| earliest[v] + dur[v]).max().unwrap_or(0);
+    let mut latest: Vec<i64> = (0..g.vs).map(|v| makespan - dur[v]).collect();
+    for &u in topo.iter().rev() {
+        for &v in g.adj[u].iter() {
+            latest[u] = latest[u].min(latest[v] - dur[u]);
+        }
+    }
+    let slack: Vec<i64> = (0..g.vs).map(|v| latest[v] - earliest[v]).collect();
+
+    // 从开始时间为 0 的关键任务出发, 每次走向紧接着开始的关键后继
+    let mut critical_path = Vec::new();
+    let mut cur = (0..g.vs).find(|&v| slack[v] == 0 && earliest[v] == 0);
+    while let Some(u) = cur {
+        critical_path.push(u);
+        cur = g.adj[u]
+            .iter()
+            .copied()
+            .filter(|&v| slack[v] == 0 && earliest[v] == earliest[u] + dur[u])
+            .min();
+    }
+    Ok(Schedule {
+        earliest,
+        latest,
+        slack,
+        makespan,
+        critical_path,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_critical_path() {
+        // 0: 开始, 1: 设计, 2: 后端, 3: 前端, 4: 文档, 5: 测试, 6: 发布
+        let dur = [0, 3, 5, 2, 1, 4, 1];
+        let mut g = Graph::new(7);
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        g.add_edge(1, 3);
+        g.add_edge(1, 4);
+        g.add_edge(2, 5);
+        g.add_edge(3, 5);
+        g.add_edge(4, 6);
+        g.add_edge(5, 6);
+        let s = critical_path(&g, &dur).unwrap();
+        assert_eq!(s.makespan, 13);
+        assert_eq!(s.earliest, vec![0, 0, 3, 3, 3, 8, 12]);
+        assert_eq!(s.latest, vec![0, 0, 3, 6, 11, 8, 12]);
+        assert_eq!(s.slack, vec![0, 0, 0, 3, 8, 0, 0]);
+        assert_eq!(s.critical_path, vec![0, 1, 2, 5, 6]);
+
+        g.add_edge(6, 1);
+        let c = critical_path(&g, &dur).unwrap_err();
+        assert_eq!(c.unordered, vec![1, 2, 3, 4, 5, 6]);
+    }
+}
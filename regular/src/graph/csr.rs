@@ -0,0 +1,143 @@
+// https://en.wikipedia.org/wiki/Sparse_matrix#Compressed_sparse_row_(CSR,_CRS_or_Yale_format)
+// 顶点 v 的邻居为 targets[offsets[v]..offsets[v + 1]], 构建后不可修改
+
+use super::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CsrGraph {
+    vs: usize,
+    offsets: Vec<usize>,
+    targets: Vec<usize>,
+    removed: Vec<bool>, // 与来源 Graph 的墓碑一致
+}
+
+impl CsrGraph {
+    // 计数排序, 同一顶点的邻居保持在 edges 中的先后顺序
+    pub fn from_edges(vs: usize, edges: &[(usize, usize)]) -> Self {
+        let mut offsets = vec![0; vs + 1];
+        for &(v, _) in edges.iter() {
+            offsets[v + 1] += 1;
+        }
+        for v in 0..vs {
+            offsets[v + 1] += offsets[v];
+        }
+        let mut next = offsets.clone();
+        let mut targets = vec![0; edges.len()];
+        for &(v, w) in edges.iter() {
+            targets[next[v]] = w;
+            next[v] += 1;
+        }
+        Self {
+            vs,
+            offsets,
+            targets,
+            removed: vec![false; vs],
+        }
+    }
+
+    pub fn from_graph(g: &Graph) -> Self {
+        let mut offsets = Vec::with_capacity(g.vs + 1);
+        let mut targets = Vec::new();
+        offsets.push(0);
+        for adj in g.adj.iter() {
+            targets.extend_from_slice(adj);
+            offsets.push(targets.len());
+        }
+        Self {
+            vs: g.vs,
+            offsets,
+            targets,
+            removed: g.removed.clone(),
+        }
+    }
+
+    pub fn edge_count(&self) -> usize {
+        self.targets.len()
+    }
+
+    pub fn adj(&self, v: usize) -> &[usize] {
+        &self.targets[self.offsets[v]..self.offsets[v + 1]]
+    }
+}
+
+impl From<&Graph> for CsrGraph {
+    fn from(g: &Graph) -> Self {
+        Self::from_graph(g)
+    }
+}
+
+impl Neighbors for CsrGraph {
+    fn vs(&self) -> usize {
+        self.vs
+    }
+
+    fn neighbors(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        self.adj(v).iter().copied()
+    }
+
+    fn is_removed(&self, v: usize) -> bool {
+        self.removed[v]
+    }
+
+    fn degree(&self, v: usize) -> usize {
+        self.offsets[v + 1] - self.offsets[v]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::sc::tarjan_scc;
+    use crate::graph::topo::toposort_kahn;
+
+    fn get_g1() -> Graph {
+        let mut g = Graph::new(6);
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        g.add_edge(2, 0);
+        g.add_edge(2, 3);
+        g.add_edge(3, 4);
+        g.add_edge(4, 5);
+        g.add_edge(5, 3);
+        g.add_edge(1, 5);
+        g
+    }
+
+    #[test]
+    fn test_csr_graph() {
+        let g1 = get_g1();
+        let c1 = CsrGraph::from(&g1);
+        let edges: Vec<(usize, usize)> = (0..g1.vs)
+            .flat_map(|v| g1.adj[v].iter().map(move |&w| (v, w)))
+            .rev()
+            .collect();
+        let mut c2 = CsrGraph::from_edges(6, &edges);
+        assert_eq!(c2.edge_count(), 8);
+        assert_eq!(c2.adj(1), &[5, 2]);
+        for v in 0..6 {
+            assert_eq!(c1.adj(v), &g1.adj[v][..]);
+        }
+        c2 = CsrGraph::from_edges(6, &edges.into_iter().rev().collect::<Vec<_>>());
+        assert_eq!(c1, c2);
+    }
+
+    #[test]
+    fn test_csr_algorithms() {
+        let g1 = get_g1();
+        let c1 = CsrGraph::from_graph(&g1);
+        let (mut a, mut b) = (Vec::new(), Vec::new());
+        bfs(&g1, 0, |v| a.push(v));
+        bfs(&c1, 0, |v| b.push(v));
+        assert_eq!(a, b);
+        assert_eq!(a, vec![0, 1, 2, 5, 3, 4]);
+        let (mut a, mut b) = (Vec::new(), Vec::new());
+        dfs(&g1, 0, |v| a.push(v));
+        dfs(&c1, 0, |v| b.push(v));
+        assert_eq!(a, b);
+        assert_eq!(tarjan_scc(&g1), tarjan_scc(&c1));
+        assert_eq!(toposort_kahn(&g1), toposort_kahn(&c1));
+
+        let dag = CsrGraph::from_edges(4, &[(3, 1), (1, 0), (3, 2), (2, 0)]);
+        assert_eq!(toposort_kahn(&dag).unwrap(), vec![3, 1, 2, 0]);
+    }
+}
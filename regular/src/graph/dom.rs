@@ -0,0 +1,244 @@
+// Cooper, Harvey, Kennedy. A Simple, Fast Dominance Algorithm
+// 按逆后序反复求前驱 idom 的交, 直到不动点
+
+use super::*;
+
+pub struct Dominators {
+    root: usize,
+    idom: Vec<usize>, // 根为自身, 不可达顶点为 usize::MAX
+    children: Vec<Vec<usize>>,
+    pre: Vec<usize>, // 支配树上的进入/离开时间, 用于 O(1) 判断支配关系
+    post: Vec<usize>,
+    frontier: Vec<Vec<usize>>,
+}
+
+// 从 root 出发的后序, 显式栈
+fn postorder(g: &Graph, root: usize) -> Vec<usize> {
+    let mut visited = vec![false; g.vs];
+    let mut order = Vec::new();
+    let mut stack = vec![(root, 0)];
+    visited[root] = true;
+    while let Some(top) = stack.last_mut() {
+        let u = top.0;
+        if top.1 == g.adj[u].len() {
+            order.push(u);
+            stack.pop();
+            continue;
+        }
+        let w = g.adj[u][top.1];
+        top.1 += 1;
+        if !visited[w] {
+            visited[w] = true;
+            stack.push((w, 0));
+        }
+    }
+    order
+}
+
+pub fn dominators(g: &Graph, root: usize) -> Dominators {
+    let order = postorder(g, root);
+    let mut po = vec![usize::MAX; g.vs];
+    for (i, &v) in order.iter().enumerate() {
+        po[v] = i;
+    }
+    let preds = g.transpose();
+    let mut idom = vec![usize::MAX; g.vs];
+    idom[root] = root;
+    let intersect = |idom: &[usize], mut a: usize, mut b: usize| {
+        while a != b {
+            while po[a] < po[b] {
+                a = idom[a];
+            }
+            while po[b] < po[a] {
+                b = idom[b];
+            }
+        }
+        a
+    };
+    let mut changed = true;
+    while changed {
+        changed = false;
+        for &v in order.iter().rev().skip(1) {
+            let mut new_idom = usize::MAX;
+            for &p in preds.adj[v].iter() {
+                if idom[p] == usize::MAX {
+                    continue;
+                }
+                new_idom = if new_idom == usize::MAX {
+                    p
+                } else {
+                    intersect(&idom, p, new_idom)
+                };
+            }
+            if idom[v] != new_idom {
+                idom[v] = new_idom;
+                changed = true;
+            }
+        }
+    }
+
+    let mut children = vec![Vec::new(); g.vs];
+    for &v in order.iter().rev().skip(1) {
+        children[idom[v]].push(v);
+    }
+    children.iter_mut().for_each(|c| c.sort_unstable());
+    let (mut pre, mut post) = (vec![0; g.vs], vec![0; g.vs]);
+    let mut clock = 0;
+    let mut stack = vec![(root, 0)];
+    pre[root] = clock;
+    while let Some(top) = stack.last_mut() {
+        let u = top.0;
+        clock += 1;
+        if top.1 == children[u].len() {
+            post[u] = clock;
+            stack.pop();
+            continue;
+        }
+        let w = children[u][top.1];
+        top.1 += 1;
+        pre[w] = clock;
+        stack.push((w, 0));
+    }
+
+    // v 的每个前驱沿支配树向上走, 直到 v 的直接支配者, 途经的顶点都以 v 为边界
+    // 根没有直接支配者, 回到根的边使途经的顶点 (含根) 都以根为边界
+    let mut frontier: Vec<Vec<usize>> = vec![Vec::new(); g.vs];
+    for &v in order.iter() {
+        let stop = if v == root { usize::MAX } else { idom[v] };
+        for &p in preds.adj[v].iter() {
+            let mut runner = p;
+            while idom[runner] != usize::MAX && runner != stop {
+                if frontier[runner].last() != Some(&v) {
+                    frontier[runner].push(v);
+                }
+                if runner == root {
+                    break;
+                }
+                runner = idom[runner];
+            }
+        }
+    }
+    frontier.iter_mut().for_each(|f| f.sort_unstable());
+
+    Dominators {
+        root,
+        idom,
+        children,
+        pre,
+        post,
+        frontier,
+    }
+}
+
+// 在反图上以 exit 为根求支配树, 边界即控制依赖
+pub fn post_dominators(g: &Graph, exit: usize) -> Dominators {
+    dominators(&g.transpose(), exit)
+}
+
+impl Dominators {
+    pub fn root(&self) -> usize {
+        self.root
+    }
+
+    pub fn is_reachable(&self, v: usize) -> bool {
+        self.idom[v] != usize::MAX
+    }
+
+    // 根和不可达顶点没有直接支配者
+    pub fn idom(&self, v: usize) -> Option<usize> {
+        if v == self.root || !self.is_reachable(v) {
+            None
+        } else {
+            Some(self.idom[v])
+        }
+    }
+
+    // 支配树上的孩子, 升序
+    pub fn children(&self, v: usize) -> &[usize] {
+        &self.children[v]
+    }
+
+    // 自反; 涉及不可达顶点时为 false
+    pub fn dominates(&self, a: usize, b: usize) -> bool {
+        self.is_reachable(a)
+            && self.is_reachable(b)
+            && self.pre[a] <= self.pre[b]
+            && self.post[b] <= self.post[a]
+    }
+
+    // 升序
+    pub fn frontier(&self, v: usize) -> &[usize] {
+        &self.frontier[v]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // 0 -> 1 -> {2, 3}, 3 -> 2 -> 4 -> {1, 5}
+    // 4 -> 1 为回边, 6 从根不可达
+    fn get_cfg() -> Graph {
+        let mut g = Graph::new(7);
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        g.add_edge(1, 3);
+        g.add_edge(3, 2);
+        g.add_edge(2, 4);
+        g.add_edge(4, 1);
+        g.add_edge(4, 5);
+        g.add_edge(6, 5);
+        g
+    }
+
+    #[test]
+    fn test_dominators() {
+        let g = get_cfg();
+        let d = dominators(&g, 0);
+        let idom: Vec<Option<usize>> = (0..7).map(|v| d.idom(v)).collect();
+        assert_eq!(
+            idom,
+            vec![None, Some(0), Some(1), Some(1), Some(2), Some(4), None]
+        );
+        assert!(d.dominates(1, 5));
+        assert!(d.dominates(2, 2));
+        assert!(!d.dominates(3, 2));
+        assert!(!d.dominates(0, 6));
+        assert_eq!(d.children(1), &[2, 3]);
+        assert_eq!(d.frontier(3), &[2]);
+        assert_eq!(d.frontier(2), &[1]);
+        assert_eq!(d.frontier(4), &[1]);
+        assert_eq!(d.frontier(1), &[1]);
+        assert!(d.frontier(0).is_empty());
+        assert!(d.frontier(5).is_empty());
+
+        // 回到根的边
+        let mut g2 = Graph::new(3);
+        g2.add_edge(0, 1);
+        g2.add_edge(1, 2);
+        g2.add_edge(2, 0);
+        let d = dominators(&g2, 0);
+        assert_eq!(d.frontier(2), &[0]);
+        assert_eq!(d.frontier(0), &[0]);
+    }
+
+    #[test]
+    fn test_post_dominators() {
+        // 菱形: 0 -> {1, 2} -> 3
+        let mut g = Graph::new(4);
+        g.add_edge(0, 1);
+        g.add_edge(0, 2);
+        g.add_edge(1, 3);
+        g.add_edge(2, 3);
+        let pd = post_dominators(&g, 3);
+        assert_eq!(pd.root(), 3);
+        assert_eq!(pd.idom(0), Some(3));
+        assert_eq!(pd.idom(1), Some(3));
+        assert!(pd.dominates(3, 0));
+        assert!(!pd.dominates(1, 0));
+        // 1 和 2 控制依赖于 0
+        assert_eq!(pd.frontier(1), &[0]);
+        assert_eq!(pd.frontier(2), &[0]);
+        assert!(pd.frontier(0).is_empty());
+    }
+}
@@ -0,0 +1,199 @@
+// Pearce, Kelly. A Dynamic Topological Sort Algorithm for Directed Acyclic Graphs
+// 每次加边只调整受影响区间 [ord[v], ord[u]] 内的顶点
+
+use std::collections::{HashMap, HashSet};
+
+use super::topo::{toposort_kahn, Cycle};
+use super::*;
+
+pub struct DynamicTopo {
+    adj: Vec<Vec<usize>>,
+    radj: Vec<Vec<usize>>,
+    ord: Vec<usize>,   // 顶点在拓扑序中的位置
+    order: Vec<usize>, // 拓扑序
+}
+
+impl DynamicTopo {
+    pub fn new(vs: usize) -> Self {
+        Self {
+            adj: vec![Vec::new(); vs],
+            radj: vec![Vec::new(); vs],
+            ord: (0..vs).collect(),
+            order: (0..vs).collect(),
+        }
+    }
+
+    // 编号与 g 一致, 因此要求 g 中没有墓碑顶点
+    pub fn from_graph(g: &Graph) -> Result<Self, Cycle> {
+        assert!(
+            g.vertices().count() == g.vs,
+            "compact the graph before building DynamicTopo"
+        );
+        let order = toposort_kahn(g)?;
+        let mut ord = vec![0; g.vs];
+        for (i, &v) in order.iter().enumerate() {
+            ord[v] = i;
+        }
+        Ok(Self {
+            adj: g.adj.clone(),
+            radj: g.transpose().adj,
+            ord,
+            order,
+        })
+    }
+
+    pub fn add_vertex(&mut self) -> usize {
+        let v = self.order.len();
+        self.adj.push(Vec::new());
+        self.radj.push(Vec::new());
+        self.ord.push(v);
+        self.order.push(v);
+        v
+    }
+
+    pub fn order(&self) -> &[usize] {
+        &self.order
+    }
+
+    pub fn position(&self, v: usize) -> usize {
+        self.ord[v]
+    }
+
+    // 从 v 出发只访问位置小于 ub 的顶点, 遇到 u 说明加边后成环
+    fn forward(&self, v: usize, u: usize, ub: usize) -> Result<Vec<usize>, Cycle> {
+        let mut visited = vec![v];
+        let mut pred = HashMap::new();
+        let mut stack = vec![v];
+        pred.insert(v, v);
+        while let Some(w) = stack.pop() {
+            for &x in self.adj[w].iter() {
+                if x == u {
+                    let mut vertices = vec![u, w];
+                    let mut y = w;
+                    while y != v {
+                        y = pred[&y];
+                        vertices.push(y);
+                    }
+                    // u -> v -> ... -> w -> u
+                    vertices[1..].reverse();
+                    return Err(Cycle {
+                        vertices,
+                        unordered: Vec::new(),
+                    });
+                }
+                if self.ord[x] < ub && !pred.contains_key(&x) {
+                    pred.insert(x, w);
+                    visited.push(x);
+                    stack.push(x);
+                }
+            }
+        }
+        Ok(visited)
+    }
+
+    // 从 u 沿反向边只访问位置大于 lb 的顶点
+    fn backward(&self, u: usize, lb: usize) -> Vec<usize> {
+        let mut visited = vec![u];
+        let mut seen = HashSet::new();
+        let mut stack = vec![u];
+        seen.insert(u);
+        while let Some(w) = stack.pop() {
+            for &x in self.radj[w].iter() {
+                if self.ord[x] > lb && seen.insert(x) {
+                    visited.push(x);
+                    stack.push(x);
+                }
+            }
+        }
+        visited
+    }
+
+    // 加边后成环时拒绝该边, 拓扑序保持不变
+    pub fn add_edge(&mut self, u: usize, v: usize) -> Result<(), Cycle> {
+        if u == v {
+            return Err(Cycle {
+                vertices: vec![u],
+                unordered: Vec::new(),
+            });
+        }
+        let (lb, ub) = (self.ord[v], self.ord[u]);
+        if lb < ub {
+            let mut delta_f = self.forward(v, u, ub)?;
+            let mut delta_b = self.backward(u, lb);
+            delta_f.sort_unstable_by_key(|&w| self.ord[w]);
+            delta_b.sort_unstable_by_key(|&w| self.ord[w]);
+            // 把 delta_b 整体挪到 delta_f 之前, 复用它们原来占据的位置
+            let mut slots: Vec<usize> = delta_b
+                .iter()
+                .chain(delta_f.iter())
+                .map(|&w| self.ord[w])
+                .collect();
+            slots.sort_unstable();
+            for (&w, &i) in delta_b.iter().chain(delta_f.iter()).zip(slots.iter()) {
+                self.ord[w] = i;
+                self.order[i] = w;
+            }
+        }
+        self.adj[u].push(v);
+        self.radj[v].push(u);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::random::Rng;
+
+    fn check_order(t: &DynamicTopo) {
+        for u in 0..t.adj.len() {
+            assert_eq!(t.order[t.ord[u]], u);
+            for &v in t.adj[u].iter() {
+                assert!(t.ord[u] < t.ord[v]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_dynamic_topo() {
+        let mut t = DynamicTopo::new(5);
+        t.add_edge(4, 3).unwrap();
+        t.add_edge(3, 2).unwrap();
+        t.add_edge(2, 1).unwrap();
+        t.add_edge(1, 0).unwrap();
+        check_order(&t);
+        assert_eq!(t.order(), &[4, 3, 2, 1, 0]);
+        let c = t.add_edge(0, 3).unwrap_err();
+        assert_eq!(c.vertices, vec![0, 3, 2, 1]);
+        assert_eq!(t.add_edge(2, 2).unwrap_err().vertices, vec![2]);
+        check_order(&t);
+        let v = t.add_vertex();
+        t.add_edge(0, v).unwrap();
+        t.add_edge(v, 4).unwrap_err();
+        check_order(&t);
+        assert_eq!(t.position(v), 5);
+    }
+
+    #[test]
+    fn test_dynamic_topo_random() {
+        let mut rng = Rng::new(5);
+        for _ in 0..20 {
+            let n = 12;
+            let mut t = DynamicTopo::new(n);
+            let mut g = Graph::new(n);
+            for _ in 0..60 {
+                let u = rng.below(n);
+                let v = rng.below(n);
+                g.add_edge(u, v);
+                let acyclic = toposort_kahn(&g).is_ok();
+                assert_eq!(t.add_edge(u, v).is_ok(), acyclic);
+                if !acyclic {
+                    g.remove_edge(u, v);
+                }
+                check_order(&t);
+            }
+            let t2 = DynamicTopo::from_graph(&g).unwrap();
+            check_order(&t2);
+        }
+    }
+}
@@ -0,0 +1,181 @@
+// https://oi-wiki.org/graph/euler/
+// Hierholzer 算法, 用显式栈代替递归
+
+use super::*;
+
+// 路径以顶点序列表示, 长度为边数加一; 回路首尾相同
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Euler {
+    Circuit(Vec<usize>),
+    Path(Vec<usize>),
+}
+
+// inc[v] 为 v 出发的边 (编号, 另一端), 每条边只能走一次
+fn hierholzer(inc: &[Vec<(usize, usize)>], edges: usize, s: usize) -> Option<Vec<usize>> {
+    let mut used = vec![false; edges];
+    let mut next = vec![0; inc.len()];
+    let mut stack = vec![s];
+    let mut path = Vec::with_capacity(edges + 1);
+    while let Some(&u) = stack.last() {
+        while next[u] < inc[u].len() && used[inc[u][next[u]].0] {
+            next[u] += 1;
+        }
+        if next[u] == inc[u].len() {
+            path.push(u);
+            stack.pop();
+        } else {
+            let (e, w) = inc[u][next[u]];
+            used[e] = true;
+            stack.push(w);
+        }
+    }
+    // 走不完说明有边不与起点连通
+    if path.len() != edges + 1 {
+        return None;
+    }
+    path.reverse();
+    Some(path)
+}
+
+// 无边时返回空回路
+pub fn directed_euler(g: &Graph) -> Option<Euler> {
+    let in_degs = g.in_degs();
+    let mut inc = vec![Vec::new(); g.vs];
+    let mut edges = 0;
+    for (v, ws) in inc.iter_mut().enumerate() {
+        for &w in g.adj[v].iter() {
+            ws.push((edges, w));
+            edges += 1;
+        }
+    }
+    if edges == 0 {
+        return Some(Euler::Circuit(Vec::new()));
+    }
+    let (mut start, mut end) = (None, None);
+    for (v, &in_deg) in in_degs.iter().enumerate() {
+        let out = g.adj[v].len();
+        if out == in_deg + 1 && start.is_none() {
+            start = Some(v);
+        } else if in_deg == out + 1 && end.is_none() {
+            end = Some(v);
+        } else if out != in_deg {
+            return None;
+        }
+    }
+    match (start, end) {
+        (Some(s), Some(_)) => hierholzer(&inc, edges, s).map(Euler::Path),
+        (None, None) => {
+            let s = (0..g.vs).find(|&v| !g.adj[v].is_empty()).unwrap();
+            hierholzer(&inc, edges, s).map(Euler::Circuit)
+        }
+        _ => None,
+    }
+}
+
+pub fn undirected_euler(g: &UnGraph) -> Option<Euler> {
+    let mut inc = vec![Vec::new(); g.vs];
+    let mut edges = 0;
+    let mut deg = vec![0; g.vs];
+    // 每条边在两端各存一次, 只在 v <= w 一端编号; 自环只存一次, 度数加二
+    for v in 0..g.vs {
+        for &w in g.adj[v].iter().filter(|&&w| v <= w) {
+            inc[v].push((edges, w));
+            if v != w {
+                inc[w].push((edges, v));
+            }
+            deg[v] += 1;
+            deg[w] += 1;
+            edges += 1;
+        }
+    }
+    if edges == 0 {
+        return Some(Euler::Circuit(Vec::new()));
+    }
+    let odd: Vec<usize> = (0..g.vs).filter(|&v| deg[v] % 2 == 1).collect();
+    match odd.len() {
+        0 => {
+            let s = (0..g.vs).find(|&v| deg[v] > 0).unwrap();
+            hierholzer(&inc, edges, s).map(Euler::Circuit)
+        }
+        2 => hierholzer(&inc, edges, odd[0]).map(Euler::Path),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check_directed(g: &Graph, path: &[usize]) {
+        let mut rest = g.adj.clone();
+        for e in path.windows(2) {
+            let i = rest[e[0]].iter().position(|&w| w == e[1]).unwrap();
+            rest[e[0]].swap_remove(i);
+        }
+        assert!(rest.iter().all(|a| a.is_empty()));
+    }
+
+    #[test]
+    fn test_directed_euler() {
+        let mut g = Graph::new(5);
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        g.add_edge(2, 0);
+        g.add_edge(1, 3);
+        g.add_edge(3, 4);
+        g.add_edge(4, 1);
+        let c = directed_euler(&g).unwrap();
+        assert_eq!(c, Euler::Circuit(vec![0, 1, 3, 4, 1, 2, 0]));
+        g.add_edge(2, 3);
+        match directed_euler(&g).unwrap() {
+            Euler::Path(p) => {
+                assert_eq!((p[0], p[p.len() - 1]), (2, 3));
+                check_directed(&g, &p);
+            }
+            c => panic!("expected path, got {:?}", c),
+        }
+        g.add_edge(2, 4);
+        assert_eq!(directed_euler(&g), None);
+
+        // 度数满足条件但不连通
+        let mut g2 = Graph::new(4);
+        g2.add_edge(0, 1);
+        g2.add_edge(1, 0);
+        g2.add_edge(2, 3);
+        g2.add_edge(3, 2);
+        assert_eq!(directed_euler(&g2), None);
+        assert_eq!(
+            directed_euler(&Graph::new(3)),
+            Some(Euler::Circuit(Vec::new()))
+        );
+    }
+
+    #[test]
+    fn test_undirected_euler() {
+        // 两个三角形共享顶点 2, 另有 2 上的自环
+        let mut g = UnGraph::new(5);
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        g.add_edge(2, 0);
+        g.add_edge(2, 3);
+        g.add_edge(3, 4);
+        g.add_edge(4, 2);
+        g.add_edge(2, 2);
+        let c = match undirected_euler(&g).unwrap() {
+            Euler::Circuit(c) => c,
+            p => panic!("expected circuit, got {:?}", p),
+        };
+        assert_eq!(c.len(), 8);
+        assert_eq!(c[0], c[7]);
+        g.add_edge(3, 4);
+        match undirected_euler(&g).unwrap() {
+            Euler::Path(p) => {
+                assert_eq!(p.len(), 9);
+                assert_eq!((p[0], p[8]), (3, 4));
+            }
+            c => panic!("expected path, got {:?}", c),
+        }
+        g.add_edge(0, 1);
+        assert_eq!(undirected_euler(&g), None);
+    }
+}
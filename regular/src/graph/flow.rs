@@ -0,0 +1,351 @@
+// https://oi-wiki.org/graph/flow/max-flow/
+
+use std::collections::VecDeque;
+
+// 每条边拆成一对弧, 弧 e 的反向弧为 e ^ 1
+pub struct FlowNetwork {
+    pub(self) vs: usize,
+    pub(self) to: Vec<usize>,
+    pub(self) cap: Vec<i64>,
+    pub(self) adj: Vec<Vec<usize>>,
+}
+
+impl FlowNetwork {
+    pub fn new(vs: usize) -> Self {
+        Self {
+            vs,
+            to: Vec::new(),
+            cap: Vec::new(),
+            adj: vec![Vec::new(); vs],
+        }
+    }
+
+    // 返回边的编号, 与 MaxFlow::flows 的下标对应
+    pub fn add_edge(&mut self, u: usize, v: usize, cap: i64) -> usize {
+        let e = self.to.len();
+        self.to.push(v);
+        self.cap.push(cap);
+        self.adj[u].push(e);
+        self.to.push(u);
+        self.cap.push(0);
+        self.adj[v].push(e + 1);
+        e / 2
+    }
+}
+
+#[derive(Debug)]
+pub struct MaxFlow {
+    pub value: i64,
+    pub flows: Vec<i64>, // 每条边上的流量
+    pub cut: Vec<bool>,  // 最小割中与源点同侧的顶点
+}
+
+impl MaxFlow {
+    fn new(net: &FlowNetwork, res: &[i64], s: usize, value: i64) -> Self {
+        let flows = (0..net.cap.len() / 2)
+            .map(|i| net.cap[2 * i] - res[2 * i])
+            .collect();
+        let mut cut = vec![false; net.vs];
+        let mut q = VecDeque::new();
+        cut[s] = true;
+        q.push_back(s);
+        while let Some(u) = q.pop_front() {
+            for &e in net.adj[u].iter() {
+                let v = net.to[e];
+                if res[e] > 0 && !cut[v] {
+                    cut[v] = true;
+                    q.push_back(v);
+                }
+            }
+        }
+        Self { value, flows, cut }
+    }
+}
+
+struct DinicContext<'a> {
+    net: &'a FlowNetwork,
+    res: Vec<i64>,     // 残量
+    level: Vec<usize>, // bfs 分层, 0 表示不可达
+    it: Vec<usize>,    // 当前弧
+}
+
+fn dinic_bfs(ctx: &mut DinicContext, s: usize, t: usize) -> bool {
+    ctx.level.iter_mut().for_each(|l| *l = 0);
+    let mut q = VecDeque::new();
+    ctx.level[s] = 1;
+    q.push_back(s);
+    while let Some(u) = q.pop_front() {
+        for &e in ctx.net.adj[u].iter() {
+            let v = ctx.net.to[e];
+            if ctx.res[e] > 0 && ctx.level[v] == 0 {
+                ctx.level[v] = ctx.level[u] + 1;
+                q.push_back(v);
+            }
+        }
+    }
+    ctx.level[t] != 0
+}
+
+// 沿当前弧在分层图上找一条增广路, 显式栈保存路径上的 (顶点, 弧), 返回增广的流量
+fn dinic_dfs(ctx: &mut DinicContext, s: usize, t: usize) -> i64 {
+    let mut stack: Vec<(usize, usize)> = Vec::new();
+    let mut u = s;
+    while u != t {
+        if ctx.it[u] == ctx.net.adj[u].len() {
+            // 死路, 回退并跳过父亲的当前弧
+            match stack.pop() {
+                Some((p, _)) => {
+                    ctx.it[p] += 1;
+                    u = p;
+                }
+                None => return 0,
+            }
+            continue;
+        }
+        let e = ctx.net.adj[u][ctx.it[u]];
+        let v = ctx.net.to[e];
+        if ctx.res[e] > 0 && ctx.level[v] == ctx.level[u] + 1 {
+            stack.push((u, e));
+            u = v;
+        } else {
+            ctx.it[u] += 1;
+        }
+    }
+    let d = stack.iter().map(|&(_, e)| ctx.res[e]).min().unwrap();
+    for &(_, e) in stack.iter() {
+        ctx.res[e] -= d;
+        ctx.res[e ^ 1] += d;
+    }
+    d
+}
+
+// 要求 s != t, 否则流量无界
+pub fn dinic(net: &FlowNetwork, s: usize, t: usize) -> MaxFlow {
+    assert!(s != t && s < net.vs && t < net.vs);
+    let mut ctx = DinicContext {
+        net,
+        res: net.cap.clone(),
+        level: vec![0; net.vs],
+        it: vec![0; net.vs],
+    };
+    let mut value = 0;
+    while dinic_bfs(&mut ctx, s, t) {
+        ctx.it.iter_mut().for_each(|i| *i = 0);
+        loop {
+            let f = dinic_dfs(&mut ctx, s, t);
+            if f == 0 {
+                break;
+            }
+            value += f;
+        }
+    }
+    MaxFlow::new(net, &ctx.res, s, value)
+}
+
+struct PushRelabelContext<'a> {
+    net: &'a FlowNetwork,
+    s: usize,
+    t: usize,
+    res: Vec<i64>,
+    height: Vec<usize>,
+    excess: Vec<i64>,
+    it: Vec<usize>,
+    count: Vec<usize>,        // 每个高度上的顶点数, 用于 gap 优化
+    buckets: Vec<Vec<usize>>, // 按高度存放的活跃顶点
+    highest: usize,
+}
+
+impl PushRelabelContext<'_> {
+    fn push(&mut self, e: usize, d: i64) {
+        let u = self.net.to[e ^ 1];
+        let v = self.net.to[e];
+        self.res[e] -= d;
+        self.res[e ^ 1] += d;
+        self.excess[u] -= d;
+        if self.excess[v] == 0 && v != self.s && v != self.t {
+            self.buckets[self.height[v]].push(v);
+            self.highest = self.highest.max(self.height[v]);
+        }
+        self.excess[v] += d;
+    }
+
+    fn pop_highest(&mut self) -> Option<usize> {
+        loop {
+            if let Some(u) = self.buckets[self.highest].pop() {
+                return Some(u);
+            }
+            if self.highest == 0 {
+                return None;
+            }
+            self.highest -= 1;
+        }
+    }
+
+    fn relabel(&mut self, u: usize) {
+        let n = self.net.vs;
+        let old = self.height[u];
+        let mut h = 2 * n;
+        for &e in self.net.adj[u].iter() {
+            if self.res[e] > 0 {
+                h = h.min(self.height[self.net.to[e]] + 1);
+            }
+        }
+        self.count[old] -= 1;
+        if self.count[old] == 0 && old < n {
+            // 高度 old 出现断层, 更高的顶点都无法再到达汇点
+            for v in 0..n {
+                if self.height[v] > old && self.height[v] < n && v != self.s {
+                    self.count[self.height[v]] -= 1;
+                    self.height[v] = n + 1;
+                    self.count[n + 1] += 1;
+                    self.it[v] = 0;
+                }
+            }
+            h = h.max(n + 1);
+        }
+        self.height[u] = h;
+        self.count[h] += 1;
+        self.it[u] = 0;
+    }
+
+    fn discharge(&mut self, u: usize) {
+        while self.excess[u] > 0 {
+            if self.it[u] == self.net.adj[u].len() {
+                self.relabel(u);
+                continue;
+            }
+            let e = self.net.adj[u][self.it[u]];
+            let v = self.net.to[e];
+            if self.res[e] > 0 && self.height[u] == self.height[v] + 1 {
+                self.push(e, self.excess[u].min(self.res[e]));
+            } else {
+                self.it[u] += 1;
+            }
+        }
+    }
+}
+
+// 最高标号预流推进 (HLPP), 与 dinic 相同要求 s != t
+pub fn push_relabel(net: &FlowNetwork, s: usize, t: usize) -> MaxFlow {
+    assert!(s != t && s < net.vs && t < net.vs);
+    let n = net.vs;
+    let mut ctx = PushRelabelContext {
+        net,
+        s,
+        t,
+        res: net.cap.clone(),
+        height: vec![0; n],
+        excess: vec![0; n],
+        it: vec![0; n],
+        count: vec![0; 2 * n + 1],
+        buckets: vec![Vec::new(); 2 * n + 1],
+        highest: 0,
+    };
+    ctx.height[s] = n;
+    ctx.count[0] = n - 1;
+    ctx.count[n] = 1;
+    for &e in net.adj[s].iter() {
+        let c = ctx.res[e];
+        if c > 0 {
+            ctx.push(e, c);
+        }
+    }
+    while let Some(u) = ctx.pop_highest() {
+        ctx.discharge(u);
+    }
+    MaxFlow::new(net, &ctx.res, s, ctx.excess[t])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::random::Rng;
+
+    fn get_net1() -> FlowNetwork {
+        let mut net = FlowNetwork::new(6);
+        net.add_edge(0, 1, 16);
+        net.add_edge(0, 2, 13);
+        net.add_edge(1, 2, 10);
+        net.add_edge(2, 1, 4);
+        net.add_edge(1, 3, 12);
+        net.add_edge(3, 2, 9);
+        net.add_edge(2, 4, 14);
+        net.add_edge(4, 3, 7);
+        net.add_edge(3, 5, 20);
+        net.add_edge(4, 5, 4);
+        net
+    }
+
+    fn check_flow(net: &FlowNetwork, f: &MaxFlow, s: usize, t: usize) {
+        let mut balance = vec![0; net.vs];
+        let mut cut_cap = 0;
+        for (i, &flow) in f.flows.iter().enumerate() {
+            let (u, v) = (net.to[2 * i + 1], net.to[2 * i]);
+            assert!(0 <= flow && flow <= net.cap[2 * i]);
+            balance[u] -= flow;
+            balance[v] += flow;
+            if f.cut[u] && !f.cut[v] {
+                cut_cap += net.cap[2 * i];
+            }
+        }
+        for (v, &b) in balance.iter().enumerate() {
+            if v != s && v != t {
+                assert_eq!(b, 0);
+            }
+        }
+        assert_eq!(balance[t], f.value);
+        assert!(f.cut[s] && !f.cut[t]);
+        assert_eq!(cut_cap, f.value);
+    }
+
+    #[test]
+    fn test_dinic() {
+        let net = get_net1();
+        let f = dinic(&net, 0, 5);
+        assert_eq!(f.value, 23);
+        check_flow(&net, &f, 0, 5);
+        assert_eq!(f.cut, vec![true, true, true, false, true, false]);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_same_source_sink() {
+        dinic(&get_net1(), 2, 2);
+    }
+
+    #[test]
+    fn test_long_chain() {
+        let n = 200_000;
+        let mut net = FlowNetwork::new(n);
+        for v in 0..n - 1 {
+            net.add_edge(v, v + 1, 1);
+        }
+        let f = dinic(&net, 0, n - 1);
+        assert_eq!(f.value, 1);
+        check_flow(&net, &f, 0, n - 1);
+    }
+
+    #[test]
+    fn test_push_relabel() {
+        let net = get_net1();
+        let f = push_relabel(&net, 0, 5);
+        assert_eq!(f.value, 23);
+        check_flow(&net, &f, 0, 5);
+
+        let mut rng = Rng::new(7);
+        for _ in 0..50 {
+            let n = 2 + rng.below(10);
+            let mut net = FlowNetwork::new(n);
+            for _ in 0..rng.below(40) {
+                let u = rng.below(n);
+                let v = rng.below(n);
+                net.add_edge(u, v, rng.below(20) as i64);
+            }
+            let f1 = dinic(&net, 0, n - 1);
+            let f2 = push_relabel(&net, 0, n - 1);
+            assert_eq!(f1.value, f2.value);
+            check_flow(&net, &f1, 0, n - 1);
+            check_flow(&net, &f2, 0, n - 1);
+        }
+    }
+}
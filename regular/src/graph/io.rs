@@ -0,0 +1,453 @@
+// https://graphviz.org/doc/info/lang.html
+
+use std::error::Error;
+use std::fmt;
+use std::fmt::Write;
+
+use super::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize, // 从 1 开始
+    pub msg: String,
+}
+
+impl ParseError {
+    fn new(line: usize, msg: impl Into<String>) -> Self {
+        Self {
+            line,
+            msg: msg.into(),
+        }
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.msg)
+    }
+}
+
+impl Error for ParseError {}
+
+const PALETTE: [&str; 8] = [
+    "#8dd3c7", "#ffffb3", "#bebada", "#fb8072", "#80b1d3", "#fdb462", "#b3de69", "#fccde5",
+];
+
+#[derive(Default)]
+pub struct DotOptions<'a> {
+    pub labels: Option<&'a [String]>, // 顶点标签, 默认为顶点编号; 每个顶点一个
+    pub path: Option<&'a [usize]>,    // 高亮的路径, 如最短路或环
+    pub scc: Option<&'a [Vec<usize>]>, // 按分量着色, 如 tarjan_scc 的结果
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+pub fn to_dot(g: &Graph, opts: &DotOptions) -> String {
+    if let Some(labels) = opts.labels {
+        assert!(
+            labels.len() >= g.vs,
+            "{} labels for {} vertices",
+            labels.len(),
+            g.vs
+        );
+    }
+    let ids = opts.path.into_iter().flatten();
+    let ids = ids.chain(opts.scc.into_iter().flatten().flatten());
+    if let Some(v) = ids.copied().find(|&v| v >= g.vs) {
+        panic!("vertex {} out of range in path or scc", v);
+    }
+    let mut on_path = vec![false; g.vs];
+    let mut path_edges = Vec::new();
+    if let Some(path) = opts.path {
+        path.iter().for_each(|&v| on_path[v] = true);
+        path_edges.extend(path.windows(2).map(|e| (e[0], e[1])));
+    }
+    let mut color = vec![None; g.vs];
+    if let Some(scc) = opts.scc {
+        for (i, comp) in scc.iter().enumerate() {
+            comp.iter()
+                .for_each(|&v| color[v] = Some(PALETTE[i % PALETTE.len()]));
+        }
+    }
+
+    let mut s = String::from("digraph {\n");
+    for v in g.vertices() {
+        let mut attrs = Vec::new();
+        if let Some(labels) = opts.labels {
+            attrs.push(format!("label=\"{}\"", escape(&labels[v])));
+        }
+        if let Some(c) = color[v] {
+            attrs.push(format!("style=filled, fillcolor=\"{}\"", c));
+        }
+        if on_path[v] {
+            attrs.push("color=red, penwidth=2".to_string());
+        }
+        if attrs.is_empty() {
+            writeln!(s, "    {};", v).unwrap();
+        } else {
+            writeln!(s, "    {} [{}];", v, attrs.join(", ")).unwrap();
+        }
+    }
+    for v in 0..g.vs {
+        for &w in g.adj[v].iter() {
+            if let Some(i) = path_edges.iter().position(|&e| e == (v, w)) {
+                // 重边只高亮一次
+                path_edges.swap_remove(i);
+                writeln!(s, "    {} -> {} [color=red, penwidth=2];", v, w).unwrap();
+            } else {
+                writeln!(s, "    {} -> {};", v, w).unwrap();
+            }
+        }
+    }
+    s.push_str("}\n");
+    s
+}
+
+// 输入中允许的顶点数上限, 避免一个很大的编号导致巨量分配
+pub const MAX_VERTICES: usize = 1 << 24;
+
+// 每行一条边 "u v", 只有一个编号的行表示孤立顶点, '#' 之后为注释
+// 顶点数为出现过的最大编号加一, 其间没有出现过的编号作为已删除的顶点
+pub fn parse_edge_list(s: &str) -> Result<Graph, ParseError> {
+    let mut edges = Vec::new();
+    let mut seen = Vec::new();
+    for (i, line) in s.lines().enumerate() {
+        let line = line.split('#').next().unwrap();
+        let mut ids = Vec::new();
+        for tok in line.split_whitespace() {
+            let id: usize = tok
+                .parse()
+                .map_err(|_| ParseError::new(i + 1, format!("invalid vertex {:?}", tok)))?;
+            if id >= MAX_VERTICES {
+                return Err(ParseError::new(
+                    i + 1,
+                    format!("vertex {} exceeds limit {}", id, MAX_VERTICES),
+                ));
+            }
+            if id >= seen.len() {
+                seen.resize(id + 1, false);
+            }
+            seen[id] = true;
+            ids.push(id);
+        }
+        match ids.len() {
+            0 | 1 => {}
+            2 => edges.push((ids[0], ids[1])),
+            n => {
+                return Err(ParseError::new(
+                    i + 1,
+                    format!("expected 2 vertices, got {}", n),
+                ))
+            }
+        }
+    }
+    let mut g = Graph::new(seen.len());
+    for (v, w) in edges {
+        g.add_edge(v, w);
+    }
+    g.removed = seen.into_iter().map(|x| !x).collect();
+    Ok(g)
+}
+
+// 已删除的顶点不输出, 由 parse_edge_list 还原为墓碑
+pub fn to_edge_list(g: &Graph) -> String {
+    let mut s = String::new();
+    let mut isolated = vec![true; g.vs];
+    for v in 0..g.vs {
+        for &w in g.adj[v].iter() {
+            writeln!(s, "{} {}", v, w).unwrap();
+            isolated[v] = false;
+            isolated[w] = false;
+        }
+    }
+    for v in g.vertices().filter(|&v| isolated[v]) {
+        writeln!(s, "{}", v).unwrap();
+    }
+    s
+}
+
+// {"vs": 3, "adj": [[1, 2], [2], []]}, 有已删除的顶点时再加上 "removed": [..]
+pub fn to_json(g: &Graph) -> String {
+    let adj: Vec<String> = g
+        .adj
+        .iter()
+        .map(|a| {
+            let ws: Vec<String> = a.iter().map(|w| w.to_string()).collect();
+            format!("[{}]", ws.join(", "))
+        })
+        .collect();
+    let removed: Vec<String> = (0..g.vs)
+        .filter(|&v| g.removed[v])
+        .map(|v| v.to_string())
+        .collect();
+    if removed.is_empty() {
+        format!("{{\"vs\": {}, \"adj\": [{}]}}", g.vs, adj.join(", "))
+    } else {
+        format!(
+            "{{\"vs\": {}, \"adj\": [{}], \"removed\": [{}]}}",
+            g.vs,
+            adj.join(", "),
+            removed.join(", ")
+        )
+    }
+}
+
+struct JsonParser<'a> {
+    s: &'a [u8],
+    pos: usize,
+}
+
+impl JsonParser<'_> {
+    fn error(&self, msg: impl Into<String>) -> ParseError {
+        let line = self.s[..self.pos].iter().filter(|&&c| c == b'\n').count() + 1;
+        ParseError::new(line, msg)
+    }
+
+    fn peek(&mut self) -> Option<u8> {
+        while self.pos < self.s.len() && self.s[self.pos].is_ascii_whitespace() {
+            self.pos += 1;
+        }
+        self.s.get(self.pos).copied()
+    }
+
+    fn expect(&mut self, c: u8) -> Result<(), ParseError> {
+        if self.peek() != Some(c) {
+            return Err(self.error(format!("expected '{}'", c as char)));
+        }
+        self.pos += 1;
+        Ok(())
+    }
+
+    // 不含转义的字符串即可满足键名
+    fn string(&mut self) -> Result<&str, ParseError> {
+        self.expect(b'"')?;
+        let start = self.pos;
+        while self.pos < self.s.len() && self.s[self.pos] != b'"' {
+            self.pos += 1;
+        }
+        if self.pos == self.s.len() {
+            return Err(self.error("unterminated string"));
+        }
+        self.pos += 1;
+        Ok(std::str::from_utf8(&self.s[start..self.pos - 1]).unwrap())
+    }
+
+    fn number(&mut self) -> Result<usize, ParseError> {
+        self.peek();
+        let start = self.pos;
+        while self.pos < self.s.len() && self.s[self.pos].is_ascii_digit() {
+            self.pos += 1;
+        }
+        std::str::from_utf8(&self.s[start..self.pos])
+            .unwrap()
+            .parse()
+            .map_err(|_| self.error("expected vertex number"))
+    }
+
+    // 以逗号分隔, 直到遇到 close
+    fn list(
+        &mut self,
+        close: u8,
+        mut item: impl FnMut(&mut Self) -> Result<(), ParseError>,
+    ) -> Result<(), ParseError> {
+        if self.peek() == Some(close) {
+            self.pos += 1;
+            return Ok(());
+        }
+        loop {
+            item(self)?;
+            if self.peek() == Some(b',') {
+                self.pos += 1;
+                continue;
+            }
+            return self.expect(close);
+        }
+    }
+}
+
+pub fn from_json(s: &str) -> Result<Graph, ParseError> {
+    let mut p = JsonParser {
+        s: s.as_bytes(),
+        pos: 0,
+    };
+    let mut vs = None;
+    let mut adj: Option<Vec<Vec<usize>>> = None;
+    let mut removed = Vec::new();
+    p.expect(b'{')?;
+    p.list(b'}', |p| {
+        let key = p.string()?.to_string();
+        p.expect(b':')?;
+        match key.as_str() {
+            "vs" => vs = Some(p.number()?),
+            "adj" => {
+                let mut a = Vec::new();
+                p.expect(b'[')?;
+                p.list(b']', |p| {
+                    let mut ws = Vec::new();
+                    p.expect(b'[')?;
+                    p.list(b']', |p| {
+                        ws.push(p.number()?);
+                        Ok(())
+                    })?;
+                    a.push(ws);
+                    Ok(())
+                })?;
+                adj = Some(a);
+            }
+            "removed" => {
+                p.expect(b'[')?;
+                p.list(b']', |p| {
+                    removed.push(p.number()?);
+                    Ok(())
+                })?;
+            }
+            _ => return Err(p.error(format!("unknown key {:?}", key))),
+        }
+        Ok(())
+    })?;
+    if p.peek().is_some() {
+        return Err(p.error("trailing characters"));
+    }
+    let adj = adj.ok_or_else(|| p.error("missing key \"adj\""))?;
+    let vs = vs.unwrap_or(adj.len());
+    if vs > MAX_VERTICES {
+        return Err(p.error(format!("{} vertices exceed limit {}", vs, MAX_VERTICES)));
+    }
+    if adj.len() != vs {
+        return Err(p.error(format!(
+            "expected {} adjacency lists, got {}",
+            vs,
+            adj.len()
+        )));
+    }
+    let mut g = Graph::new(vs);
+    for v in removed {
+        if v >= vs {
+            return Err(p.error(format!("vertex {} out of range", v)));
+        }
+        g.removed[v] = true;
+    }
+    for (v, ws) in adj.into_iter().enumerate() {
+        for w in ws {
+            if w >= vs {
+                return Err(p.error(format!("vertex {} out of range", w)));
+            }
+            if g.removed[v] || g.removed[w] {
+                return Err(p.error(format!("edge {} -> {} touches a removed vertex", v, w)));
+            }
+            g.add_edge(v, w);
+        }
+    }
+    Ok(g)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::sc::tarjan_scc;
+
+    fn get_g1() -> Graph {
+        let mut g = Graph::new(6);
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        g.add_edge(2, 0);
+        g.add_edge(2, 3);
+        g.add_edge(3, 4);
+        g.add_edge(3, 4);
+        g
+    }
+
+    #[test]
+    fn test_to_dot() {
+        let g1 = get_g1();
+        let labels: Vec<String> = ["a", "b", "c", "d\"", "e", "f"]
+            .iter()
+            .map(|s| s.to_string())
+            .collect();
+        let scc = tarjan_scc(&g1);
+        let dot = to_dot(
+            &g1,
+            &DotOptions {
+                labels: Some(&labels),
+                path: Some(&[2, 3, 4]),
+                scc: Some(&scc),
+            },
+        );
+        println!("{}", dot);
+        assert!(dot.starts_with("digraph {\n"));
+        assert!(dot.contains("3 [label=\"d\\\"\""));
+        assert!(dot.contains("2 -> 3 [color=red, penwidth=2];"));
+        assert!(dot.contains("3 -> 4 [color=red, penwidth=2];\n    3 -> 4;\n"));
+        assert!(dot.contains("    0 -> 1;\n"));
+        assert_eq!(
+            to_dot(&Graph::new(1), &DotOptions::default()),
+            "digraph {\n    0;\n}\n"
+        );
+    }
+
+    #[test]
+    #[should_panic(expected = "out of range")]
+    fn test_to_dot_bad_path() {
+        let opts = DotOptions {
+            path: Some(&[0, 4]),
+            ..Default::default()
+        };
+        to_dot(&Graph::new(3), &opts);
+    }
+
+    #[test]
+    fn test_edge_list() {
+        let g1 = get_g1();
+        let s = to_edge_list(&g1);
+        assert_eq!(s, "0 1\n1 2\n2 0\n2 3\n3 4\n3 4\n5\n");
+        let g2 = parse_edge_list(&s).unwrap();
+        assert_eq!(g2.vs, g1.vs);
+        assert_eq!(g2.adj, g1.adj);
+
+        let g3 = parse_edge_list("# deps\n0 1\n\n  1\t2  # b -> c\n4\n").unwrap();
+        assert_eq!(g3.vs, 5);
+        assert_eq!(g3.adj, vec![vec![1], vec![2], vec![], vec![], vec![]]);
+        assert!(g3.is_removed(3) && !g3.is_removed(4));
+        assert_eq!(
+            parse_edge_list("0 1\n1 x\n").unwrap_err(),
+            ParseError::new(2, "invalid vertex \"x\"")
+        );
+        assert_eq!(parse_edge_list("0 1 2").unwrap_err().line, 1);
+        assert_eq!(
+            parse_edge_list("0 1\n18446744073709551615\n")
+                .unwrap_err()
+                .line,
+            2
+        );
+        assert!(parse_edge_list("0 99999999999").is_err());
+    }
+
+    #[test]
+    fn test_json() {
+        let g1 = get_g1();
+        let s = to_json(&g1);
+        assert_eq!(
+            s,
+            "{\"vs\": 6, \"adj\": [[1], [2], [0, 3], [4, 4], [], []]}"
+        );
+        let g2 = from_json(&s).unwrap();
+        assert_eq!(g2.vs, g1.vs);
+        assert_eq!(g2.adj, g1.adj);
+
+        let g3 = from_json("{\n  \"adj\": [[1],\n [] ]\n}").unwrap();
+        assert_eq!(g3.vs, 2);
+        assert_eq!(from_json("{\"adj\": [[1]]}").unwrap_err().line, 1);
+        assert_eq!(
+            from_json("{\"vs\": 1,\n\"adj\": [[0]],\n}")
+                .unwrap_err()
+                .line,
+            3
+        );
+        assert!(from_json("{\"vs\": 1, \"adj\": [[0]]} x").is_err());
+        let g4 = from_json("{\"vs\": 3, \"adj\": [[2], [], []], \"removed\": [1]}").unwrap();
+        assert!(g4.is_removed(1));
+        assert!(from_json("{\"adj\": [[1], []], \"removed\": [1]}").is_err());
+    }
+}
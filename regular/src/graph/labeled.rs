@@ -0,0 +1,139 @@
+// 以任意键标识顶点, 内部把键映射为连续编号, 复用 Graph 上的算法
+
+use std::collections::HashMap;
+use std::hash::Hash;
+
+use super::sc::tarjan_scc;
+use super::topo::{toposort_kahn, Cycle};
+use super::*;
+
+pub struct LabeledGraph<K: Hash + Eq> {
+    ids: HashMap<K, usize>,
+    keys: Vec<K>,
+    g: Graph,
+}
+
+impl<K: Hash + Eq + Clone> LabeledGraph<K> {
+    pub fn new() -> Self {
+        Self {
+            ids: HashMap::new(),
+            keys: Vec::new(),
+            g: Graph::new(0),
+        }
+    }
+
+    // 已存在时返回原编号
+    pub fn add_vertex(&mut self, key: K) -> usize {
+        if let Some(&id) = self.ids.get(&key) {
+            return id;
+        }
+        let id = self.g.add_vertex();
+        self.ids.insert(key.clone(), id);
+        self.keys.push(key);
+        id
+    }
+
+    // 不存在的端点会自动加入
+    pub fn add_edge(&mut self, from: K, to: K) {
+        let v = self.add_vertex(from);
+        let w = self.add_vertex(to);
+        self.g.add_edge(v, w);
+    }
+}
+
+impl<K: Hash + Eq + Clone> Default for LabeledGraph<K> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<K: Hash + Eq> LabeledGraph<K> {
+    pub fn id(&self, key: &K) -> Option<usize> {
+        self.ids.get(key).copied()
+    }
+
+    pub fn key(&self, id: usize) -> &K {
+        &self.keys[id]
+    }
+
+    pub fn keys(&self, ids: &[usize]) -> Vec<&K> {
+        ids.iter().map(|&id| &self.keys[id]).collect()
+    }
+
+    pub fn graph(&self) -> &Graph {
+        &self.g
+    }
+
+    pub fn successors(&self, key: &K) -> Vec<&K> {
+        self.id(key)
+            .map_or(Vec::new(), |v| self.keys(&self.g.adj[v]))
+    }
+
+    // 与 tarjan_scc 相同, 分量按逆拓扑序
+    pub fn scc(&self) -> Vec<Vec<&K>> {
+        tarjan_scc(&self.g).iter().map(|c| self.keys(c)).collect()
+    }
+
+    // 有环时 Cycle 中的顶点也转换为键
+    pub fn toposort(&self) -> Result<Vec<&K>, Cycle<&K>> {
+        match toposort_kahn(&self.g) {
+            Ok(order) => Ok(self.keys(&order)),
+            Err(c) => Err(Cycle {
+                vertices: self.keys(&c.vertices),
+                unordered: self.keys(&c.unordered),
+            }),
+        }
+    }
+}
+
+impl<K: Hash + Eq> Neighbors for LabeledGraph<K> {
+    fn vs(&self) -> usize {
+        self.g.vs
+    }
+
+    fn neighbors(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        self.g.neighbors(v)
+    }
+
+    fn degree(&self, v: usize) -> usize {
+        self.g.adj[v].len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_labeled_graph() {
+        let mut g = LabeledGraph::new();
+        g.add_edge("app", "http");
+        g.add_edge("app", "log");
+        g.add_edge("http", "tls");
+        g.add_edge("http", "log");
+        g.add_vertex("docs");
+        assert_eq!(g.add_vertex("http"), 1);
+        assert_eq!(g.id(&"tls"), Some(3));
+        assert_eq!(g.id(&"none"), None);
+        assert_eq!(*g.key(4), "docs");
+        assert_eq!(g.graph().vs, 5);
+        assert_eq!(g.successors(&"http"), vec![&"tls", &"log"]);
+        assert_eq!(
+            g.toposort().unwrap(),
+            vec![&"app", &"docs", &"http", &"tls", &"log"]
+        );
+
+        g.add_edge("tls", "app");
+        let scc = g.scc();
+        assert!(scc.contains(&vec![&"log"]));
+        let mut cyclic = scc.into_iter().find(|c| c.len() > 1).unwrap();
+        cyclic.sort();
+        assert_eq!(cyclic, vec![&"app", &"http", &"tls"]);
+        let c = g.toposort().unwrap_err();
+        assert_eq!(c.unordered, vec![&"app", &"http", &"log", &"tls"]);
+        assert_eq!(
+            c.to_string(),
+            "cycle detected: http -> tls -> app -> http (4 vertices unordered)"
+        );
+    }
+}
@@ -0,0 +1,275 @@
+// https://oi-wiki.org/graph/graph-matching/bigraph-match/
+
+use std::collections::VecDeque;
+
+// 左部顶点 0..ls, 右部顶点 0..rs, 边只从左部指向右部
+pub struct BipartiteGraph {
+    pub(self) ls: usize,
+    pub(self) rs: usize,
+    pub(self) adj: Vec<Vec<usize>>,
+}
+
+impl BipartiteGraph {
+    pub fn new(ls: usize, rs: usize) -> Self {
+        Self {
+            ls,
+            rs,
+            adj: vec![Vec::new(); ls],
+        }
+    }
+
+    pub fn add_edge(&mut self, u: usize, v: usize) {
+        assert!(v < self.rs);
+        self.adj[u].push(v);
+    }
+}
+
+#[derive(Debug)]
+pub struct Matching {
+    pub pairs: Vec<(usize, usize)>, // (左部顶点, 右部顶点), 按左部升序
+    pub cover_left: Vec<usize>,     // König 最小点覆盖中的左部顶点
+    pub cover_right: Vec<usize>,    // König 最小点覆盖中的右部顶点
+}
+
+struct HopcroftKarpContext<'a> {
+    g: &'a BipartiteGraph,
+    match_l: Vec<Option<usize>>,
+    match_r: Vec<Option<usize>>,
+    dist: Vec<usize>, // 左部顶点在交替路上的层数, 0 表示不在本轮分层图中
+    limit: usize,     // 最短增广路的终点所在层, 本轮只沿这一长度增广
+}
+
+// 从所有未匹配左部顶点出发分层, 遇到第一个未匹配右部顶点后不再向更深层扩展
+// 返回是否存在增广路
+fn hopcroft_karp_bfs(ctx: &mut HopcroftKarpContext) -> bool {
+    let mut q = VecDeque::new();
+    for u in 0..ctx.g.ls {
+        if ctx.match_l[u].is_none() {
+            ctx.dist[u] = 1;
+            q.push_back(u);
+        } else {
+            ctx.dist[u] = 0;
+        }
+    }
+    ctx.limit = usize::MAX;
+    while let Some(u) = q.pop_front() {
+        if ctx.dist[u] >= ctx.limit {
+            break;
+        }
+        for &v in ctx.g.adj[u].iter() {
+            match ctx.match_r[v] {
+                None => ctx.limit = ctx.dist[u],
+                Some(w) => {
+                    if ctx.dist[w] == 0 {
+                        ctx.dist[w] = ctx.dist[u] + 1;
+                        q.push_back(w);
+                    }
+                }
+            }
+        }
+    }
+    ctx.limit != usize::MAX
+}
+
+fn hopcroft_karp_dfs(ctx: &mut HopcroftKarpContext, u: usize) -> bool {
+    for i in 0..ctx.g.adj[u].len() {
+        let v = ctx.g.adj[u][i];
+        let ok = match ctx.match_r[v] {
+            None => ctx.dist[u] == ctx.limit,
+            Some(w) => {
+                ctx.dist[u] < ctx.limit
+                    && ctx.dist[w] == ctx.dist[u] + 1
+                    && hopcroft_karp_dfs(ctx, w)
+            }
+        };
+        if ok {
+            ctx.match_l[u] = Some(v);
+            ctx.match_r[v] = Some(u);
+            return true;
+        }
+    }
+    // 本轮不再经过 u
+    ctx.dist[u] = 0;
+    false
+}
+
+pub fn hopcroft_karp(g: &BipartiteGraph) -> Matching {
+    let mut ctx = HopcroftKarpContext {
+        g,
+        match_l: vec![None; g.ls],
+        match_r: vec![None; g.rs],
+        dist: vec![0; g.ls],
+        limit: usize::MAX,
+    };
+    while hopcroft_karp_bfs(&mut ctx) {
+        for u in 0..g.ls {
+            if ctx.match_l[u].is_none() {
+                hopcroft_karp_dfs(&mut ctx, u);
+            }
+        }
+    }
+
+    // 从未匹配左部顶点出发沿交替路可达的顶点集为 Z, 覆盖为 (L - Z) + (R ∩ Z)
+    let mut zl = vec![false; g.ls];
+    let mut zr = vec![false; g.rs];
+    let mut q = VecDeque::new();
+    for (u, m) in ctx.match_l.iter().enumerate() {
+        if m.is_none() {
+            zl[u] = true;
+            q.push_back(u);
+        }
+    }
+    while let Some(u) = q.pop_front() {
+        for &v in g.adj[u].iter() {
+            if zr[v] || ctx.match_l[u] == Some(v) {
+                continue;
+            }
+            zr[v] = true;
+            if let Some(w) = ctx.match_r[v] {
+                if !zl[w] {
+                    zl[w] = true;
+                    q.push_back(w);
+                }
+            }
+        }
+    }
+
+    Matching {
+        pairs: (0..g.ls)
+            .filter_map(|u| ctx.match_l[u].map(|v| (u, v)))
+            .collect(),
+        cover_left: (0..g.ls).filter(|&u| !zl[u]).collect(),
+        cover_right: (0..g.rs).filter(|&v| zr[v]).collect(),
+    }
+}
+
+// https://oi-wiki.org/graph/graph-matching/bigraph-weight-match/
+// 最小费用完美分配, 要求行数不超过列数, 返回总费用和 (行, 列) 对
+pub fn hungarian(cost: &[Vec<i64>]) -> (i64, Vec<(usize, usize)>) {
+    let n = cost.len();
+    if n == 0 {
+        return (0, Vec::new());
+    }
+    let m = cost[0].len();
+    assert!(n <= m);
+    // 下标从 1 开始, 列 0 为虚拟列
+    let mut u = vec![0i64; n + 1];
+    let mut v = vec![0i64; m + 1];
+    let mut p = vec![0usize; m + 1]; // 列匹配的行
+    let mut way = vec![0usize; m + 1];
+    for i in 1..=n {
+        p[0] = i;
+        let mut j0 = 0;
+        let mut minv = vec![i64::MAX; m + 1];
+        let mut used = vec![false; m + 1];
+        loop {
+            used[j0] = true;
+            let i0 = p[j0];
+            let mut delta = i64::MAX;
+            let mut j1 = 0;
+            for j in 1..=m {
+                if used[j] {
+                    continue;
+                }
+                let cur = cost[i0 - 1][j - 1] - u[i0] - v[j];
+                if cur < minv[j] {
+                    minv[j] = cur;
+                    way[j] = j0;
+                }
+                if minv[j] < delta {
+                    delta = minv[j];
+                    j1 = j;
+                }
+            }
+            for j in 0..=m {
+                if used[j] {
+                    u[p[j]] += delta;
+                    v[j] -= delta;
+                } else {
+                    minv[j] -= delta;
+                }
+            }
+            j0 = j1;
+            if p[j0] == 0 {
+                break;
+            }
+        }
+        // 沿 way 回溯翻转增广路
+        while j0 != 0 {
+            let j1 = way[j0];
+            p[j0] = p[j1];
+            j0 = j1;
+        }
+    }
+    let mut pairs: Vec<(usize, usize)> = (1..=m)
+        .filter(|&j| p[j] != 0)
+        .map(|j| (p[j] - 1, j - 1))
+        .collect();
+    pairs.sort_unstable();
+    let total = pairs.iter().map(|&(i, j)| cost[i][j]).sum();
+    (total, pairs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::random::Rng;
+
+    #[test]
+    fn test_hopcroft_karp() {
+        let mut g = BipartiteGraph::new(5, 5);
+        g.add_edge(0, 0);
+        g.add_edge(0, 1);
+        g.add_edge(1, 0);
+        g.add_edge(2, 1);
+        g.add_edge(2, 2);
+        g.add_edge(2, 3);
+        g.add_edge(3, 1);
+        g.add_edge(4, 1);
+        let m = hopcroft_karp(&g);
+        assert_eq!(m.pairs.len(), 3);
+        assert_eq!(m.cover_left.len() + m.cover_right.len(), 3);
+        for u in 0..g.ls {
+            for &v in g.adj[u].iter() {
+                assert!(m.cover_left.contains(&u) || m.cover_right.contains(&v));
+            }
+        }
+        let mut rs: Vec<usize> = m.pairs.iter().map(|p| p.1).collect();
+        rs.sort_unstable();
+        rs.dedup();
+        assert_eq!(rs.len(), 3);
+        assert!(m.pairs.iter().all(|&(u, v)| g.adj[u].contains(&v)));
+    }
+
+    fn brute_force(cost: &[Vec<i64>], i: usize, used: &mut [bool]) -> i64 {
+        if i == cost.len() {
+            return 0;
+        }
+        let mut best = i64::MAX;
+        for j in 0..used.len() {
+            if !used[j] {
+                used[j] = true;
+                best = best.min(cost[i][j] + brute_force(cost, i + 1, used));
+                used[j] = false;
+            }
+        }
+        best
+    }
+
+    #[test]
+    fn test_hungarian() {
+        let cost = vec![vec![4, 1, 3], vec![2, 0, 5], vec![3, 2, 2]];
+        assert_eq!(hungarian(&cost), (5, vec![(0, 1), (1, 0), (2, 2)]));
+
+        let mut rng = Rng::new(1);
+        for _ in 0..20 {
+            let mut cost = vec![vec![0; 6]; 4];
+            for c in cost.iter_mut().flatten() {
+                *c = rng.below(50) as i64 - 10;
+            }
+            let (total, pairs) = hungarian(&cost);
+            assert_eq!(total, brute_force(&cost, 0, &mut [false; 6]));
+            assert_eq!(pairs.len(), 4);
+        }
+    }
+}
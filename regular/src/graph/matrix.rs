@@ -0,0 +1,133 @@
+// https://oi-wiki.org/graph/save/#邻接矩阵
+// 邻接矩阵按行存为位图, 适合稠密图; 不支持重边
+
+use super::*;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MatrixGraph {
+    vs: usize,
+    words: usize,
+    directed: bool,
+    rows: Vec<u64>, // 第 v 行为 v 的邻居集合, 每行 words 个字
+}
+
+impl MatrixGraph {
+    pub fn new(vs: usize) -> Self {
+        Self::with_mode(vs, true)
+    }
+
+    // 无向图, 加边时同时设置 (v, w) 与 (w, v)
+    pub fn new_undirected(vs: usize) -> Self {
+        Self::with_mode(vs, false)
+    }
+
+    fn with_mode(vs: usize, directed: bool) -> Self {
+        let words = vs.div_ceil(64);
+        Self {
+            vs,
+            words,
+            directed,
+            rows: vec![0; vs * words],
+        }
+    }
+
+    pub fn from_graph(g: &Graph) -> Self {
+        let mut m = Self::new(g.vs);
+        for v in 0..g.vs {
+            for &w in g.adj[v].iter() {
+                m.add_edge(v, w);
+            }
+        }
+        m
+    }
+
+    pub fn is_directed(&self) -> bool {
+        self.directed
+    }
+
+    fn row(&self, v: usize) -> &[u64] {
+        &self.rows[v * self.words..(v + 1) * self.words]
+    }
+
+    fn set(&mut self, v: usize, w: usize) {
+        self.rows[v * self.words + w / 64] |= 1 << (w % 64);
+    }
+
+    pub fn add_edge(&mut self, v: usize, w: usize) {
+        self.set(v, w);
+        if !self.directed {
+            self.set(w, v);
+        }
+    }
+
+    pub fn has_edge(&self, v: usize, w: usize) -> bool {
+        self.row(v)[w / 64] & (1 << (w % 64)) != 0
+    }
+
+    // 无向图每条边只计一次
+    pub fn edge_count(&self) -> usize {
+        let total: usize = self.rows.iter().map(|x| x.count_ones() as usize).sum();
+        if self.directed {
+            total
+        } else {
+            let loops = (0..self.vs).filter(|&v| self.has_edge(v, v)).count();
+            (total + loops) / 2
+        }
+    }
+}
+
+impl Neighbors for MatrixGraph {
+    fn vs(&self) -> usize {
+        self.vs
+    }
+
+    // 按编号升序
+    fn neighbors(&self, v: usize) -> impl Iterator<Item = usize> + '_ {
+        self.row(v).iter().enumerate().flat_map(|(i, &x)| {
+            let mut x = x;
+            std::iter::from_fn(move || {
+                if x == 0 {
+                    return None;
+                }
+                let b = x.trailing_zeros() as usize;
+                x &= x - 1;
+                Some(i * 64 + b)
+            })
+        })
+    }
+
+    fn degree(&self, v: usize) -> usize {
+        self.row(v).iter().map(|x| x.count_ones() as usize).sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_matrix_graph() {
+        let mut m = MatrixGraph::new_undirected(130);
+        m.add_edge(0, 1);
+        m.add_edge(1, 0);
+        m.add_edge(1, 129);
+        m.add_edge(64, 64);
+        assert!(!m.is_directed());
+        assert!(m.has_edge(129, 1));
+        assert_eq!(m.edge_count(), 3);
+        assert_eq!(m.neighbors(1).collect::<Vec<_>>(), vec![0, 129]);
+        assert_eq!(m.degree(64), 1);
+
+        let mut g = Graph::new(3);
+        g.add_edge(0, 2);
+        g.add_edge(0, 1);
+        g.add_edge(2, 0);
+        let m = MatrixGraph::from_graph(&g);
+        assert!(m.has_edge(0, 2) && !m.has_edge(1, 0));
+        assert_eq!(m.edge_count(), 3);
+        assert_eq!(m.in_degs(), vec![1, 1, 1]);
+        let mut order = Vec::new();
+        bfs(&m, 0, |v| order.push(v));
+        assert_eq!(order, vec![0, 1, 2]);
+    }
+}
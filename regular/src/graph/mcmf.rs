@@ -0,0 +1,202 @@
+// https://oi-wiki.org/graph/flow/min-cost/
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+// 与 FlowNetwork 相同, 弧 e 的反向弧为 e ^ 1, 反向弧费用取反
+pub struct CostFlowNetwork {
+    pub(self) vs: usize,
+    pub(self) to: Vec<usize>,
+    pub(self) cap: Vec<i64>,
+    pub(self) cost: Vec<i64>,
+    pub(self) adj: Vec<Vec<usize>>,
+}
+
+impl CostFlowNetwork {
+    pub fn new(vs: usize) -> Self {
+        Self {
+            vs,
+            to: Vec::new(),
+            cap: Vec::new(),
+            cost: Vec::new(),
+            adj: vec![Vec::new(); vs],
+        }
+    }
+
+    pub fn add_edge(&mut self, u: usize, v: usize, cap: i64, cost: i64) -> usize {
+        let e = self.to.len();
+        self.to.push(v);
+        self.cap.push(cap);
+        self.cost.push(cost);
+        self.adj[u].push(e);
+        self.to.push(u);
+        self.cap.push(0);
+        self.cost.push(-cost);
+        self.adj[v].push(e + 1);
+        e / 2
+    }
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct MinCostFlow {
+    pub flow: i64,
+    pub cost: i64,
+    pub flows: Vec<i64>, // 每条边上的流量
+}
+
+// 初始残量网络中可能有负费用边, 先用 Bellman-Ford 求势能, 有负环时返回 None
+fn initial_potential(net: &CostFlowNetwork, s: usize) -> Option<Vec<i64>> {
+    let mut h: Vec<Option<i64>> = vec![None; net.vs];
+    h[s] = Some(0);
+    for _ in 0..net.vs {
+        let mut changed = false;
+        for u in 0..net.vs {
+            let hu = match h[u] {
+                Some(hu) => hu,
+                None => continue,
+            };
+            for &e in net.adj[u].iter() {
+                let v = net.to[e];
+                if net.cap[e] > 0 && h[v].is_none_or(|hv| hu + net.cost[e] < hv) {
+                    h[v] = Some(hu + net.cost[e]);
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            return Some(h.into_iter().map(|h| h.unwrap_or(0)).collect());
+        }
+    }
+    None
+}
+
+// 以 h 为势能, 在约化费用 cost + h[u] - h[v] >= 0 上跑 Dijkstra
+fn dijkstra(
+    net: &CostFlowNetwork,
+    res: &[i64],
+    h: &[i64],
+    s: usize,
+) -> (Vec<Option<i64>>, Vec<Option<usize>>) {
+    let mut dist = vec![None; net.vs];
+    let mut pred = vec![None; net.vs];
+    let mut done = vec![false; net.vs];
+    let mut heap = BinaryHeap::new();
+    dist[s] = Some(0);
+    heap.push(Reverse((0, s)));
+    while let Some(Reverse((du, u))) = heap.pop() {
+        if done[u] {
+            continue;
+        }
+        done[u] = true;
+        for &e in net.adj[u].iter() {
+            if res[e] == 0 {
+                continue;
+            }
+            let v = net.to[e];
+            let dv = du + net.cost[e] + h[u] - h[v];
+            if dist[v].is_none_or(|d| dv < d) {
+                dist[v] = Some(dv);
+                pred[v] = Some(e);
+                heap.push(Reverse((dv, v)));
+            }
+        }
+    }
+    (dist, pred)
+}
+
+// 连续最短路, 最多送出 limit 单位的流; 要求 s != t, 否则流量无界
+pub fn min_cost_flow(net: &CostFlowNetwork, s: usize, t: usize, limit: i64) -> Option<MinCostFlow> {
+    assert!(s != t && s < net.vs && t < net.vs);
+    let mut h = initial_potential(net, s)?;
+    let mut res = net.cap.clone();
+    let mut flow = 0;
+    let mut cost = 0;
+    while flow < limit {
+        let (dist, pred) = dijkstra(net, &res, &h, s);
+        if dist[t].is_none() {
+            break;
+        }
+        // 不可达的顶点此后也不会再可达, 势能无需更新
+        for v in 0..net.vs {
+            if let Some(d) = dist[v] {
+                h[v] += d;
+            }
+        }
+        let mut d = limit - flow;
+        let mut v = t;
+        while let Some(e) = pred[v] {
+            d = d.min(res[e]);
+            v = net.to[e ^ 1];
+        }
+        let mut v = t;
+        while let Some(e) = pred[v] {
+            res[e] -= d;
+            res[e ^ 1] += d;
+            cost += d * net.cost[e];
+            v = net.to[e ^ 1];
+        }
+        flow += d;
+    }
+    let flows = (0..net.cap.len() / 2)
+        .map(|i| net.cap[2 * i] - res[2 * i])
+        .collect();
+    Some(MinCostFlow { flow, cost, flows })
+}
+
+pub fn min_cost_max_flow(net: &CostFlowNetwork, s: usize, t: usize) -> Option<MinCostFlow> {
+    min_cost_flow(net, s, t, i64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_min_cost_max_flow() {
+        // 3 个工人分配 3 个任务
+        let cost = [[4, 1, 3], [2, 0, 5], [3, 2, 2]];
+        let mut net = CostFlowNetwork::new(8);
+        for (i, row) in cost.iter().enumerate() {
+            net.add_edge(0, 1 + i, 1, 0);
+            net.add_edge(4 + i, 7, 1, 0);
+            for (j, &c) in row.iter().enumerate() {
+                net.add_edge(1 + i, 4 + j, 1, c);
+            }
+        }
+        let r = min_cost_max_flow(&net, 0, 7).unwrap();
+        assert_eq!(r.flow, 3);
+        assert_eq!(r.cost, 5);
+
+        let r = min_cost_flow(&net, 0, 7, 2).unwrap();
+        assert_eq!(r.flow, 2);
+        assert_eq!(r.cost, 2);
+    }
+
+    #[test]
+    fn test_negative_cost() {
+        let mut net = CostFlowNetwork::new(4);
+        net.add_edge(0, 1, 2, 1);
+        net.add_edge(0, 2, 2, 4);
+        net.add_edge(1, 2, 1, -3);
+        net.add_edge(1, 3, 1, 2);
+        net.add_edge(2, 3, 3, 1);
+        let r = min_cost_max_flow(&net, 0, 3).unwrap();
+        assert_eq!(r.flow, 4);
+        assert_eq!(r.cost, 12);
+        assert_eq!(r.flows, vec![2, 2, 1, 1, 3]);
+
+        let mut net = CostFlowNetwork::new(3);
+        net.add_edge(0, 1, 1, 1);
+        net.add_edge(1, 2, 1, -2);
+        net.add_edge(2, 1, 1, 1);
+        assert_eq!(min_cost_max_flow(&net, 0, 2), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_same_source_sink() {
+        let mut net = CostFlowNetwork::new(2);
+        net.add_edge(0, 1, 1, 1);
+        min_cost_max_flow(&net, 0, 0);
+    }
+}
@@ -0,0 +1,153 @@
+// https://oi-wiki.org/graph/mst/
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use super::*;
+use crate::dsu::DisjointSet;
+
+// 图不连通时为最小生成森林
+#[derive(Debug, PartialEq, Eq)]
+pub struct SpanningForest {
+    pub edges: Vec<usize>, // 选中边的编号, 升序
+    pub weight: i64,
+}
+
+impl SpanningForest {
+    fn new(g: &WeightedUnGraph, mut edges: Vec<usize>) -> Self {
+        edges.sort_unstable();
+        let weight = edges.iter().map(|&e| g.edges[e].2).sum();
+        Self { edges, weight }
+    }
+}
+
+// 边权相同时按编号比较, 保证三种算法得到同一棵树
+fn edge_key(g: &WeightedUnGraph, e: usize) -> (i64, usize) {
+    (g.edges[e].2, e)
+}
+
+pub fn kruskal(g: &WeightedUnGraph) -> SpanningForest {
+    let mut order: Vec<usize> = (0..g.edges.len()).collect();
+    order.sort_unstable_by_key(|&e| edge_key(g, e));
+    let mut set = DisjointSet::new(g.vs);
+    let mut edges = Vec::new();
+    for e in order {
+        let (u, v, _) = g.edges[e];
+        if set.union(u, v) {
+            edges.push(e);
+        }
+    }
+    SpanningForest::new(g, edges)
+}
+
+pub fn prim(g: &WeightedUnGraph) -> SpanningForest {
+    let mut visited = vec![false; g.vs];
+    let mut heap = BinaryHeap::new();
+    let mut edges = Vec::new();
+    for root in 0..g.vs {
+        if visited[root] {
+            continue;
+        }
+        visited[root] = true;
+        for &e in g.adj[root].iter() {
+            heap.push(Reverse((edge_key(g, e), root)));
+        }
+        while let Some(Reverse(((_, e), from))) = heap.pop() {
+            let (u, v, _) = g.edges[e];
+            let to = if u == from { v } else { u };
+            if visited[to] {
+                continue;
+            }
+            visited[to] = true;
+            edges.push(e);
+            for &e in g.adj[to].iter() {
+                heap.push(Reverse((edge_key(g, e), to)));
+            }
+        }
+    }
+    SpanningForest::new(g, edges)
+}
+
+pub fn boruvka(g: &WeightedUnGraph) -> SpanningForest {
+    let mut set = DisjointSet::new(g.vs);
+    let mut edges = Vec::new();
+    loop {
+        // 每个连通块连出去的最小边
+        let mut cheapest: Vec<Option<usize>> = vec![None; g.vs];
+        for (e, &(u, v, _)) in g.edges.iter().enumerate() {
+            let (cu, cv) = (set.find(u), set.find(v));
+            if cu == cv {
+                continue;
+            }
+            for &c in [cu, cv].iter() {
+                if cheapest[c].is_none_or(|b| edge_key(g, e) < edge_key(g, b)) {
+                    cheapest[c] = Some(e);
+                }
+            }
+        }
+        let mut merged = false;
+        for e in cheapest.into_iter().flatten() {
+            let (u, v, _) = g.edges[e];
+            if set.union(u, v) {
+                edges.push(e);
+                merged = true;
+            }
+        }
+        if !merged {
+            break;
+        }
+    }
+    SpanningForest::new(g, edges)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_g1() -> WeightedUnGraph {
+        let mut g = WeightedUnGraph::new(9);
+        g.add_edge(0, 1, 4);
+        g.add_edge(0, 7, 8);
+        g.add_edge(1, 2, 8);
+        g.add_edge(1, 7, 11);
+        g.add_edge(2, 3, 7);
+        g.add_edge(2, 8, 2);
+        g.add_edge(2, 5, 4);
+        g.add_edge(3, 4, 9);
+        g.add_edge(3, 5, 14);
+        g.add_edge(4, 5, 10);
+        g.add_edge(5, 6, 2);
+        g.add_edge(6, 7, 1);
+        g.add_edge(6, 8, 6);
+        g.add_edge(7, 8, 7);
+        g
+    }
+
+    #[test]
+    fn test_mst() {
+        let g1 = get_g1();
+        let t = kruskal(&g1);
+        assert_eq!(t.weight, 37);
+        assert_eq!(t.edges.len(), 8);
+        assert_eq!(prim(&g1), t);
+        assert_eq!(boruvka(&g1), t);
+    }
+
+    #[test]
+    fn test_spanning_forest() {
+        let mut g = WeightedUnGraph::new(7);
+        g.add_edge(0, 1, 3);
+        g.add_edge(1, 2, 1);
+        g.add_edge(0, 2, 1);
+        g.add_edge(0, 0, -5);
+        g.add_edge(3, 4, 2);
+        g.add_edge(4, 5, 2);
+        g.add_edge(3, 5, 2);
+        g.add_edge(3, 4, -1);
+        let f = kruskal(&g);
+        assert_eq!(f.edges, vec![1, 2, 5, 7]);
+        assert_eq!(f.weight, 3);
+        assert_eq!(prim(&g), f);
+        assert_eq!(boruvka(&g), f);
+    }
+}
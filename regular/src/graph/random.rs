@@ -0,0 +1,280 @@
+// https://en.wikipedia.org/wiki/Random_graph
+// 相同的种子生成相同的图, 用于性质测试和基准测试
+
+use crate::rand::PseudoRand;
+
+use super::*;
+
+// 线性同余的低位周期很短, 只取高位; 也供其他模块的测试使用
+pub(crate) struct Rng(PseudoRand);
+
+impl Rng {
+    pub(crate) fn new(seed: u64) -> Self {
+        Self(PseudoRand::new(seed))
+    }
+
+    pub(crate) fn below(&mut self, n: usize) -> usize {
+        ((self.0.rand() >> 32) % n as u64) as usize
+    }
+
+    pub(crate) fn chance(&mut self, p: f64) -> bool {
+        ((self.0.rand() >> 11) as f64 / (1u64 << 53) as f64) < p
+    }
+
+    fn shuffle(&mut self, a: &mut [usize]) {
+        for i in (1..a.len()).rev() {
+            a.swap(i, self.below(i + 1));
+        }
+    }
+
+    fn permutation(&mut self, n: usize) -> Vec<usize> {
+        let mut a: Vec<usize> = (0..n).collect();
+        self.shuffle(&mut a);
+        a
+    }
+}
+
+// G(n, p), 每对顶点独立地以概率 p 连边, 无自环; 无向时双向存储
+pub fn erdos_renyi(n: usize, p: f64, directed: bool, seed: u64) -> Graph {
+    let mut rng = Rng::new(seed);
+    let mut g = Graph::new(n);
+    for u in 0..n {
+        for v in 0..n {
+            if u == v || (!directed && v < u) || !rng.chance(p) {
+                continue;
+            }
+            g.add_edge(u, v);
+            if !directed {
+                g.add_edge(v, u);
+            }
+        }
+    }
+    g
+}
+
+// 在随机排列上只连从前往后的边, 顶点编号与拓扑序无关
+pub fn random_dag(n: usize, p: f64, seed: u64) -> Graph {
+    let mut rng = Rng::new(seed);
+    let perm = rng.permutation(n);
+    let mut g = Graph::new(n);
+    for i in 0..n {
+        for j in i + 1..n {
+            if rng.chance(p) {
+                g.add_edge(perm[i], perm[j]);
+            }
+        }
+    }
+    g
+}
+
+// 从 m + 1 个顶点的完全图开始, 每个新顶点按度数比例连向 m 个不同的已有顶点
+pub fn barabasi_albert(n: usize, m: usize, seed: u64) -> UnGraph {
+    assert!(m >= 1 && n > m);
+    let mut rng = Rng::new(seed);
+    let mut g = UnGraph::new(n);
+    let mut ends = Vec::new(); // 每条边的两个端点各出现一次, 均匀抽取即按度数加权
+    for u in 0..=m {
+        for v in u + 1..=m {
+            g.add_edge(u, v);
+            ends.push(u);
+            ends.push(v);
+        }
+    }
+    for v in m + 1..n {
+        let mut targets: Vec<usize> = Vec::with_capacity(m);
+        while targets.len() < m {
+            let t = ends[rng.below(ends.len())];
+            if !targets.contains(&t) {
+                targets.push(t);
+            }
+        }
+        for t in targets {
+            g.add_edge(v, t);
+            ends.push(v);
+            ends.push(t);
+        }
+    }
+    g
+}
+
+// 顶点 (r, c) 的编号为 r * cols + c
+pub fn grid(rows: usize, cols: usize) -> UnGraph {
+    let mut g = UnGraph::new(rows * cols);
+    for r in 0..rows {
+        for c in 0..cols {
+            let v = r * cols + c;
+            if c + 1 < cols {
+                g.add_edge(v, v + 1);
+            }
+            if r + 1 < rows {
+                g.add_edge(v, v + cols);
+            }
+        }
+    }
+    g
+}
+
+// 随机递归树, 以 0 为根, 边从父亲指向孩子, 可直接交给 Tree::new
+pub fn random_tree(n: usize, seed: u64) -> Graph {
+    let mut rng = Rng::new(seed);
+    let mut g = Graph::new(n);
+    for v in 1..n {
+        g.add_edge(rng.below(v), v);
+    }
+    g
+}
+
+// 每组顶点先串成一个环保证强连通, 再加 extra 条随机边; 组间的边只从前一组指向后一组
+// 返回图和预期的强连通分量, 每个分量内顶点升序; 每组至少一个顶点
+pub fn planted_scc(sizes: &[usize], extra: usize, seed: u64) -> (Graph, Vec<Vec<usize>>) {
+    assert!(sizes.iter().all(|&size| size >= 1), "empty group");
+    assert!(!sizes.is_empty() || extra == 0, "no group for extra edges");
+    let mut rng = Rng::new(seed);
+    let n: usize = sizes.iter().sum();
+    let perm = rng.permutation(n);
+    let mut groups = Vec::with_capacity(sizes.len());
+    let mut start = 0;
+    for &size in sizes.iter() {
+        groups.push(perm[start..start + size].to_vec());
+        start += size;
+    }
+    let mut g = Graph::new(n);
+    for group in groups.iter() {
+        if group.len() > 1 {
+            for i in 0..group.len() {
+                g.add_edge(group[i], group[(i + 1) % group.len()]);
+            }
+        }
+    }
+    for _ in 0..extra {
+        let a = rng.below(groups.len());
+        let b = a + rng.below(groups.len() - a);
+        let u = groups[a][rng.below(groups[a].len())];
+        let v = groups[b][rng.below(groups[b].len())];
+        g.add_edge(u, v);
+    }
+    for group in groups.iter_mut() {
+        group.sort_unstable();
+    }
+    (g, groups)
+}
+
+// 随机 DAG 上再加一个长度为 len 的环, 返回图和环上的顶点
+pub fn planted_cycle(n: usize, p: f64, len: usize, seed: u64) -> (Graph, Vec<usize>) {
+    assert!(len >= 1 && len <= n);
+    let mut g = random_dag(n, p, seed);
+    let mut rng = Rng::new(!seed);
+    let cycle = rng.permutation(n)[..len].to_vec();
+    for i in 0..len {
+        g.add_edge(cycle[i], cycle[(i + 1) % len]);
+    }
+    (g, cycle)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graph::conn::{connected_components, degree_stats};
+    use crate::graph::dyntopo::DynamicTopo;
+    use crate::graph::sc::{is_sc, kosaraju_scc, tarjan_scc};
+    use crate::graph::topo::{toposort_dfs, toposort_kahn, toposort_levels, toposort_lexical};
+    use crate::graph::tree::Tree;
+
+    fn normalize(mut scc: Vec<Vec<usize>>) -> Vec<Vec<usize>> {
+        scc.iter_mut().for_each(|c| c.sort_unstable());
+        scc.sort_unstable();
+        scc
+    }
+
+    fn check_order(g: &Graph, order: &[usize]) {
+        assert_eq!(order.len(), g.vs);
+        let mut pos = vec![usize::MAX; g.vs];
+        for (i, &v) in order.iter().enumerate() {
+            assert_eq!(pos[v], usize::MAX);
+            pos[v] = i;
+        }
+        for u in 0..g.vs {
+            for &v in g.adj[u].iter() {
+                assert!(pos[u] < pos[v]);
+            }
+        }
+    }
+
+    #[test]
+    fn test_generators() {
+        let a = erdos_renyi(30, 0.2, true, 7);
+        let b = erdos_renyi(30, 0.2, true, 7);
+        assert_eq!(a.adj, b.adj);
+        let un = erdos_renyi(30, 0.2, false, 7);
+        for u in 0..30 {
+            for &v in un.adj[u].iter() {
+                assert!(un.has_edge(v, u));
+            }
+        }
+
+        let ba = barabasi_albert(200, 3, 1);
+        assert_eq!(ba.edge_count(), 6 + (200 - 4) * 3);
+        assert_eq!(connected_components(&ba).len(), 1);
+        assert!(degree_stats(&ba).min >= 3);
+
+        let gr = grid(3, 4);
+        assert_eq!(gr.edge_count(), 3 * 3 + 2 * 4);
+        assert_eq!(degree_stats(&gr).hist, vec![0, 0, 4, 6, 2]);
+
+        let t = Tree::new(&random_tree(100, 3), 0).unwrap();
+        assert_eq!(t.subtree_size(0), 100);
+
+        let (g, scc) = planted_scc(&[], 0, 1);
+        assert_eq!((g.vs, scc.len()), (0, 0));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_planted_scc_empty_group() {
+        planted_scc(&[3, 0, 2], 5, 1);
+    }
+
+    #[test]
+    fn test_scc_properties() {
+        for seed in 0..50 {
+            let g = erdos_renyi(1 + seed as usize % 20, 0.15, true, seed);
+            let scc = tarjan_scc(&g);
+            assert_eq!(is_sc(&g), scc.len() == 1);
+            assert_eq!(normalize(scc), normalize(kosaraju_scc(&g)));
+
+            let sizes: Vec<usize> = (0..1 + seed % 5)
+                .map(|i| 1 + (seed + i) as usize % 6)
+                .collect();
+            let (g, expect) = planted_scc(&sizes, 30, seed);
+            assert_eq!(normalize(tarjan_scc(&g)), normalize(expect.clone()));
+            assert_eq!(is_sc(&g), expect.len() == 1);
+        }
+    }
+
+    #[test]
+    fn test_topo_properties() {
+        for seed in 0..50 {
+            let n = 1 + seed as usize % 40;
+            let g = random_dag(n, 0.1, seed);
+            let kahn = toposort_kahn(&g).unwrap();
+            check_order(&g, &kahn);
+            check_order(&g, &toposort_dfs(&g).unwrap());
+            let lexical = toposort_lexical(&g).unwrap();
+            check_order(&g, &lexical);
+            let levels: Vec<usize> = toposort_levels(&g).unwrap().concat();
+            check_order(&g, &levels);
+            let dt = DynamicTopo::from_graph(&g).unwrap();
+            check_order(&g, dt.order());
+            // 字典序最小的拓扑序不大于其他任何一个
+            assert!(lexical <= kahn);
+
+            let (g, cycle) = planted_cycle(n, 0.1, 1 + seed as usize % n, seed);
+            assert!(toposort_kahn(&g).is_err());
+            assert!(toposort_dfs(&g).is_err());
+            assert!(toposort_lexical(&g).is_err());
+            assert!(toposort_levels(&g).is_err());
+            let scc = tarjan_scc(&g);
+            assert!(scc.iter().any(|c| cycle.iter().all(|v| c.contains(v))));
+        }
+    }
+}
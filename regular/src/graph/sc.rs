@@ -3,46 +3,59 @@
 
 use super::*;
 
+// 只考虑未删除的顶点
 pub fn is_sc(g: &Graph) -> bool {
+    let root = match g.vertices().next() {
+        Some(root) => root,
+        None => return true,
+    };
     let mut visited = vec![false; g.vs];
-    dfs_recur(g, 0, &mut visited, &mut |_| {});
-    if !visited.into_iter().all(|x| x) {
+    dfs_iter(g, root, &mut visited, &mut |_| {});
+    if !g.vertices().all(|v| visited[v]) {
         return false;
     }
     let mut visited = vec![false; g.vs];
     let r = g.transpose();
-    dfs_recur(&r, 0, &mut visited, &mut |_| {});
-    visited.into_iter().all(|x| x)
+    dfs_iter(&r, root, &mut visited, &mut |_| {});
+    g.vertices().all(|v| visited[v])
+}
+
+pub(super) struct TarjanContext<'a, G = Graph> {
+    pub(super) g: &'a G,
+    pub(super) dfn_cnt: usize,
+    pub(super) dfn: Vec<usize>, // dfs 时顶点 u 被搜索的编号
+    pub(super) low: Vec<usize>, // 顶点 u 回溯到的最早编号
+    pub(super) stack: Vec<usize>,
+    pub(super) in_stack: Vec<bool>,
+    pub(super) scc: Vec<Vec<usize>>,
 }
 
-struct TarjanContext<'a> {
-    g: &'a Graph,
-    dfn_cnt: usize,
-    dfn: Vec<usize>, // dfs 时顶点 u 被搜索的编号
-    low: Vec<usize>, // 顶点 u 回溯到的最早编号
-    stack: Vec<usize>,
-    in_stack: Vec<bool>,
-    scc: Vec<Vec<usize>>,
+impl<'a, G: Neighbors> TarjanContext<'a, G> {
+    pub(super) fn new(g: &'a G) -> Self {
+        Self {
+            g,
+            dfn: vec![0; g.vs()],
+            low: vec![0; g.vs()],
+            dfn_cnt: 0,
+            stack: Vec::new(),
+            in_stack: vec![false; g.vs()],
+            scc: Vec::new(),
+        }
+    }
 }
 
-fn tarjan_scc_recur(ctx: &mut TarjanContext, u: usize) {
+pub(super) fn tarjan_enter<G>(ctx: &mut TarjanContext<G>, u: usize) {
     ctx.dfn_cnt += 1;
     ctx.dfn[u] = ctx.dfn_cnt;
     ctx.low[u] = ctx.dfn_cnt;
     ctx.stack.push(u);
     ctx.in_stack[u] = true;
-    for &v in ctx.g.adj[u].iter() {
-        if ctx.dfn[v] == 0 {
-            tarjan_scc_recur(ctx, v);
-            ctx.low[u] = ctx.low[u].min(ctx.low[v]);
-        } else if ctx.in_stack[v] {
-            ctx.low[u] = ctx.low[u].min(ctx.dfn[v]);
-        }
-    }
+}
+
+pub(super) fn tarjan_leave<G>(ctx: &mut TarjanContext<G>, u: usize) {
     if ctx.dfn[u] == ctx.low[u] {
         let mut scc = Vec::new();
-        while let Some(&top) = ctx.stack.last() {
-            ctx.stack.pop();
+        while let Some(top) = ctx.stack.pop() {
             ctx.in_stack[top] = false;
             scc.push(top);
             if top == u {
@@ -53,25 +66,113 @@ fn tarjan_scc_recur(ctx: &mut TarjanContext, u: usize) {
     }
 }
 
-pub fn tarjan_scc(g: &Graph) -> Vec<Vec<usize>> {
-    let mut ctx = TarjanContext {
-        g,
-        dfn: vec![0; g.vs],
-        low: vec![0; g.vs],
-        dfn_cnt: 0,
-        stack: Vec::new(),
-        in_stack: vec![false; g.vs],
-        scc: Vec::new(),
-    };
-    for u in 0..g.vs {
+// 用显式栈模拟递归, 避免长链导致栈溢出
+fn tarjan_scc_iter<G: Neighbors>(ctx: &mut TarjanContext<G>, root: usize) {
+    let g = ctx.g;
+    let mut call = vec![(root, g.neighbors(root))]; // 顶点, 尚未访问的邻居
+    tarjan_enter(ctx, root);
+    while let Some((u, it)) = call.last_mut() {
+        let u = *u;
+        if let Some(v) = it.next() {
+            if ctx.dfn[v] == 0 {
+                tarjan_enter(ctx, v);
+                call.push((v, g.neighbors(v)));
+            } else if ctx.in_stack[v] {
+                ctx.low[u] = ctx.low[u].min(ctx.dfn[v]);
+            }
+            continue;
+        }
+        call.pop();
+        tarjan_leave(ctx, u);
+        if let Some(&(p, _)) = call.last() {
+            ctx.low[p] = ctx.low[p].min(ctx.low[u]);
+        }
+    }
+}
+
+pub fn tarjan_scc<G: Neighbors>(g: &G) -> Vec<Vec<usize>> {
+    let mut ctx = TarjanContext::new(g);
+    for u in g.vertices() {
         if ctx.dfn[u] != 0 {
             continue;
         }
-        tarjan_scc_recur(&mut ctx, u);
+        tarjan_scc_iter(&mut ctx, u);
     }
     ctx.scc
 }
 
+// https://oi-wiki.org/graph/scc/#kosaraju-算法
+// 返回的分量按缩点后的拓扑序排列
+pub fn kosaraju_scc(g: &Graph) -> Vec<Vec<usize>> {
+    // 第一遍求后序
+    let mut order = Vec::with_capacity(g.vs);
+    let mut visited = vec![false; g.vs];
+    for root in g.vertices() {
+        if visited[root] {
+            continue;
+        }
+        visited[root] = true;
+        let mut stack = vec![(root, 0)];
+        while let Some(top) = stack.last_mut() {
+            let u = top.0;
+            if top.1 < g.adj[u].len() {
+                let v = g.adj[u][top.1];
+                top.1 += 1;
+                if !visited[v] {
+                    visited[v] = true;
+                    stack.push((v, 0));
+                }
+                continue;
+            }
+            stack.pop();
+            order.push(u);
+        }
+    }
+    // 第二遍按后序逆序在反图上搜索
+    let r = g.transpose();
+    let mut visited = vec![false; g.vs];
+    let mut scc = Vec::new();
+    for &u in order.iter().rev() {
+        if visited[u] {
+            continue;
+        }
+        let mut comp = Vec::new();
+        dfs_iter(&r, u, &mut visited, &mut |v| comp.push(v));
+        scc.push(comp);
+    }
+    scc
+}
+
+// 已删除的顶点不属于任何分量, comp 中为 usize::MAX
+pub struct Condensation {
+    pub comp: Vec<usize>,         // 顶点所属分量, 分量编号即缩点后的一个拓扑序
+    pub dag: Graph,               // 分量之间的边, 已去重
+    pub members: Vec<Vec<usize>>, // 分量包含的顶点
+}
+
+pub fn condensation(g: &Graph) -> Condensation {
+    let mut members = tarjan_scc(g);
+    // tarjan 按逆拓扑序给出分量
+    members.reverse();
+    let mut comp = vec![usize::MAX; g.vs];
+    for (c, scc) in members.iter().enumerate() {
+        for &v in scc.iter() {
+            comp[v] = c;
+        }
+    }
+    let mut dag = Graph::new(members.len());
+    for u in 0..g.vs {
+        for &v in g.adj[u].iter() {
+            if comp[u] != comp[v] {
+                dag.add_edge(comp[u], comp[v]);
+            }
+        }
+    }
+    dag.adj.iter_mut().for_each(|adj| adj.sort_unstable());
+    dag.dedup_edges();
+    Condensation { comp, dag, members }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -104,4 +205,96 @@ mod tests {
         let scc = tarjan_scc(&g1);
         println!("scc = {:?}", scc);
     }
+
+    fn get_g2() -> Graph {
+        let mut g = Graph::new(8);
+        g.add_edge(0, 1);
+        g.add_edge(1, 2);
+        g.add_edge(2, 0);
+        g.add_edge(2, 3);
+        g.add_edge(1, 3);
+        g.add_edge(3, 4);
+        g.add_edge(4, 5);
+        g.add_edge(5, 3);
+        g.add_edge(6, 5);
+        g.add_edge(6, 7);
+        g.add_edge(7, 6);
+        g
+    }
+
+    // 递归版本, 只作为 tarjan_scc_iter 的参照
+    fn tarjan_scc_recur(ctx: &mut TarjanContext, u: usize) {
+        tarjan_enter(ctx, u);
+        for v in ctx.g.neighbors(u) {
+            if ctx.dfn[v] == 0 {
+                tarjan_scc_recur(ctx, v);
+                ctx.low[u] = ctx.low[u].min(ctx.low[v]);
+            } else if ctx.in_stack[v] {
+                ctx.low[u] = ctx.low[u].min(ctx.dfn[v]);
+            }
+        }
+        tarjan_leave(ctx, u);
+    }
+
+    fn tarjan_scc_ref(g: &Graph) -> Vec<Vec<usize>> {
+        let mut ctx = TarjanContext::new(g);
+        for u in g.vertices() {
+            if ctx.dfn[u] == 0 {
+                tarjan_scc_recur(&mut ctx, u);
+            }
+        }
+        ctx.scc
+    }
+
+    #[test]
+    fn test_tarjan_scc_iter() {
+        assert_eq!(tarjan_scc(&get_g2()), tarjan_scc_ref(&get_g2()));
+        for seed in 0..30 {
+            let n = 1 + seed as usize % 25;
+            let g = random::erdos_renyi(n, 0.15, true, seed);
+            assert_eq!(tarjan_scc(&g), tarjan_scc_ref(&g));
+            let (g, _) = random::planted_scc(&[1 + n % 4, 3, 1 + n % 7], 10, seed);
+            assert_eq!(tarjan_scc(&g), tarjan_scc_ref(&g));
+            let g = random::random_dag(n, 0.2, seed);
+            assert_eq!(tarjan_scc(&g), tarjan_scc_ref(&g));
+        }
+    }
+
+    #[test]
+    fn test_kosaraju_scc() {
+        let g2 = get_g2();
+        let normalize = |mut scc: Vec<Vec<usize>>| {
+            scc.iter_mut().for_each(|c| c.sort_unstable());
+            scc.sort();
+            scc
+        };
+        let scc = kosaraju_scc(&g2);
+        assert_eq!(normalize(scc.clone()), normalize(tarjan_scc(&g2)),);
+        assert_eq!(scc.len(), 3);
+        assert_eq!(scc[2].len(), 3);
+        assert!(scc[2].contains(&3));
+    }
+
+    #[test]
+    fn test_condensation() {
+        let g2 = get_g2();
+        let c = condensation(&g2);
+        assert_eq!(c.members.len(), 3);
+        assert_eq!(c.comp[0], c.comp[2]);
+        assert_eq!(c.comp[3], c.comp[5]);
+        assert_eq!(c.comp[6], c.comp[7]);
+        for (i, m) in c.members.iter().enumerate() {
+            assert!(m.iter().all(|&v| c.comp[v] == i));
+        }
+        assert_eq!(c.dag.adj[c.comp[0]], vec![c.comp[3]]);
+        assert_eq!(c.dag.adj[c.comp[6]], vec![c.comp[3]]);
+        assert!(c.dag.adj[c.comp[3]].is_empty());
+        let topo = crate::graph::topo::toposort_kahn(&c.dag).unwrap();
+        assert_eq!(topo.len(), 3);
+        for u in 0..g2.vs {
+            for &v in g2.adj[u].iter() {
+                assert!(c.comp[u] <= c.comp[v]);
+            }
+        }
+    }
 }
@@ -0,0 +1,241 @@
+// https://oi-wiki.org/graph/shortest-path/
+
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+
+use super::*;
+
+// 从初始距离开始松弛, 最短路至多 vs - 1 条边, 第 vs + 1 轮仍有更新说明存在负环
+fn bellman_ford_from(g: &WeightedGraph, mut dist: Vec<Option<i64>>) -> Option<Vec<Option<i64>>> {
+    for _ in 0..=g.vs {
+        let mut changed = false;
+        for u in 0..g.vs {
+            let du = match dist[u] {
+                Some(du) => du,
+                None => continue,
+            };
+            for &(v, w) in g.adj[u].iter() {
+                if dist[v].is_none_or(|dv| du + w < dv) {
+                    dist[v] = Some(du + w);
+                    changed = true;
+                }
+            }
+        }
+        if !changed {
+            return Some(dist);
+        }
+    }
+    None
+}
+
+pub fn bellman_ford(g: &WeightedGraph, s: usize) -> Option<Vec<Option<i64>>> {
+    let mut dist = vec![None; g.vs];
+    dist[s] = Some(0);
+    bellman_ford_from(g, dist)
+}
+
+fn dijkstra_with(
+    g: &WeightedGraph,
+    s: usize,
+    weight: impl Fn(usize, usize, i64) -> i64,
+) -> (Vec<Option<i64>>, Vec<Option<usize>>) {
+    let mut dist = vec![None; g.vs];
+    let mut pred = vec![None; g.vs];
+    let mut done = vec![false; g.vs];
+    let mut heap = BinaryHeap::new();
+    dist[s] = Some(0);
+    heap.push(Reverse((0, s)));
+    while let Some(Reverse((du, u))) = heap.pop() {
+        if done[u] {
+            continue;
+        }
+        done[u] = true;
+        for &(v, w) in g.adj[u].iter() {
+            let dv = du + weight(u, v, w);
+            if dist[v].is_none_or(|d| dv < d) {
+                dist[v] = Some(dv);
+                pred[v] = Some(u);
+                heap.push(Reverse((dv, v)));
+            }
+        }
+    }
+    (dist, pred)
+}
+
+// 要求边权非负, 返回距离和最短路树中的前驱
+pub fn dijkstra(g: &WeightedGraph, s: usize) -> (Vec<Option<i64>>, Vec<Option<usize>>) {
+    dijkstra_with(g, s, |_, _, w| w)
+}
+
+pub struct DistMatrix {
+    vs: usize,
+    dist: Vec<Option<i64>>,
+    pred: Vec<Option<usize>>, // u 到 v 的最短路上 v 的前驱
+}
+
+impl DistMatrix {
+    fn new(vs: usize) -> Self {
+        Self {
+            vs,
+            dist: vec![None; vs * vs],
+            pred: vec![None; vs * vs],
+        }
+    }
+
+    pub fn dist(&self, u: usize, v: usize) -> Option<i64> {
+        self.dist[u * self.vs + v]
+    }
+
+    pub fn path(&self, u: usize, v: usize) -> Option<Vec<usize>> {
+        self.dist(u, v)?;
+        let mut path = vec![v];
+        let mut w = v;
+        while w != u {
+            w = self.pred[u * self.vs + w]?;
+            path.push(w);
+        }
+        path.reverse();
+        Some(path)
+    }
+}
+
+// 存在负环时返回 None
+pub fn floyd_warshall(g: &WeightedGraph) -> Option<DistMatrix> {
+    let n = g.vs;
+    let mut m = DistMatrix::new(n);
+    for u in 0..n {
+        m.dist[u * n + u] = Some(0);
+    }
+    for u in 0..n {
+        for &(v, w) in g.adj[u].iter() {
+            if m.dist[u * n + v].is_none_or(|d| w < d) {
+                m.dist[u * n + v] = Some(w);
+                m.pred[u * n + v] = Some(u);
+            }
+        }
+    }
+    for k in 0..n {
+        for i in 0..n {
+            let dik = match m.dist[i * n + k] {
+                Some(dik) => dik,
+                None => continue,
+            };
+            for j in 0..n {
+                let dkj = match m.dist[k * n + j] {
+                    Some(dkj) => dkj,
+                    None => continue,
+                };
+                if m.dist[i * n + j].is_none_or(|d| dik + dkj < d) {
+                    m.dist[i * n + j] = Some(dik + dkj);
+                    m.pred[i * n + j] = m.pred[k * n + j];
+                }
+            }
+        }
+        // 负环上的距离每轮成倍减小, 必须立即停止, 否则很快溢出
+        if (0..n).any(|u| m.dist[u * n + u].is_some_and(|d| d < 0)) {
+            return None;
+        }
+    }
+    Some(m)
+}
+
+// https://oi-wiki.org/graph/shortest-path/#johnson-全源最短路径算法
+pub fn johnson(g: &WeightedGraph) -> Option<DistMatrix> {
+    let n = g.vs;
+    // 相当于虚拟源点向所有顶点连一条权为 0 的边
+    let h: Vec<i64> = bellman_ford_from(g, vec![Some(0); n])?
+        .into_iter()
+        .map(|d| d.unwrap())
+        .collect();
+    let mut m = DistMatrix::new(n);
+    for s in 0..n {
+        let (dist, pred) = dijkstra_with(g, s, |u, v, w| w + h[u] - h[v]);
+        for v in 0..n {
+            m.dist[s * n + v] = dist[v].map(|d| d - h[s] + h[v]);
+            m.pred[s * n + v] = pred[v];
+        }
+    }
+    Some(m)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn get_g1() -> WeightedGraph {
+        let mut g = WeightedGraph::new(5);
+        g.add_edge(0, 1, 3);
+        g.add_edge(0, 2, 8);
+        g.add_edge(0, 4, -4);
+        g.add_edge(1, 3, 1);
+        g.add_edge(1, 4, 7);
+        g.add_edge(2, 1, 4);
+        g.add_edge(3, 0, 2);
+        g.add_edge(3, 2, -5);
+        g.add_edge(4, 3, 6);
+        g
+    }
+
+    #[test]
+    fn test_floyd_warshall() {
+        let g1 = get_g1();
+        let m = floyd_warshall(&g1).unwrap();
+        assert_eq!(m.dist(0, 1), Some(1));
+        assert_eq!(m.dist(0, 3), Some(2));
+        assert_eq!(m.path(0, 1), Some(vec![0, 4, 3, 2, 1]));
+        assert_eq!(m.path(2, 2), Some(vec![2]));
+        assert_eq!(m.dist(1, 0), Some(3));
+
+        let mut g2 = WeightedGraph::new(3);
+        g2.add_edge(0, 1, 1);
+        g2.add_edge(1, 0, -2);
+        assert!(floyd_warshall(&g2).is_none());
+        assert!(johnson(&g2).is_none());
+
+        let mut g3 = WeightedGraph::new(80);
+        for u in 0..80 {
+            for v in 0..80 {
+                if u != v {
+                    g3.add_edge(u, v, -1_000_000);
+                }
+            }
+        }
+        assert!(floyd_warshall(&g3).is_none());
+
+        let g4 = WeightedGraph::new(0);
+        assert!(floyd_warshall(&g4).is_some());
+        assert!(johnson(&g4).is_some());
+    }
+
+    #[test]
+    fn test_johnson() {
+        let g1 = get_g1();
+        let m1 = floyd_warshall(&g1).unwrap();
+        let m2 = johnson(&g1).unwrap();
+        for u in 0..5 {
+            for v in 0..5 {
+                assert_eq!(m1.dist(u, v), m2.dist(u, v));
+                let path = m2.path(u, v).unwrap();
+                let len: i64 = path
+                    .windows(2)
+                    .map(|e| {
+                        g1.adj[e[0]]
+                            .iter()
+                            .filter(|x| x.0 == e[1])
+                            .map(|x| x.1)
+                            .min()
+                            .unwrap()
+                    })
+                    .sum();
+                assert_eq!(Some(len), m2.dist(u, v));
+            }
+        }
+
+        let mut g2 = WeightedGraph::new(3);
+        g2.add_edge(0, 1, 2);
+        let m = johnson(&g2).unwrap();
+        assert_eq!(m.dist(1, 0), None);
+        assert_eq!(m.path(1, 0), None);
+        assert_eq!(m.path(0, 1), Some(vec![0, 1]));
+    }
+}
@@ -1,68 +1,250 @@
 // https://oi-wiki.org/graph/topo/
 
+use std::cmp::Reverse;
+use std::collections::BinaryHeap;
+use std::error::Error;
+use std::fmt;
+
 use super::*;
 
-pub fn toposort_kahn(g: &Graph) -> Vec<usize> {
+// 顶点默认为编号, LabeledGraph 中为键
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cycle<V = usize> {
+    pub vertices: Vec<V>,  // 环上的顶点, 按边的方向排列, 最后一个顶点连回第一个
+    pub unordered: Vec<V>, // 无法排序的全部顶点, 升序, 仅 toposort_kahn 给出
+}
+
+impl<V: fmt::Display> fmt::Display for Cycle<V> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cycle detected: ")?;
+        for v in self.vertices.iter() {
+            write!(f, "{} -> ", v)?;
+        }
+        write!(f, "{}", self.vertices[0])?;
+        if !self.unordered.is_empty() {
+            write!(f, " ({} vertices unordered)", self.unordered.len())?;
+        }
+        Ok(())
+    }
+}
+
+impl<V: fmt::Debug + fmt::Display> Error for Cycle<V> {}
+
+// 剩余顶点的入度都不为 0, 沿着剩余的前驱一直回溯必然会走进一个环
+fn find_cycle_kahn<G: Neighbors>(g: &G, remaining: &[bool]) -> Cycle {
+    let unordered: Vec<usize> = (0..g.vs()).filter(|&v| remaining[v]).collect();
+    // 每个剩余顶点任取一个剩余的前驱
+    let mut pred = vec![usize::MAX; g.vs()];
+    for &u in unordered.iter() {
+        for v in g.neighbors(u) {
+            if remaining[v] {
+                pred[v] = u;
+            }
+        }
+    }
+    let mut pos = vec![usize::MAX; g.vs()];
+    let mut path = Vec::new();
+    let mut v = unordered[0];
+    while pos[v] == usize::MAX {
+        pos[v] = path.len();
+        path.push(v);
+        v = pred[v];
+    }
+    let mut vertices = path.split_off(pos[v]);
+    vertices.reverse();
+    Cycle {
+        vertices,
+        unordered,
+    }
+}
+
+// 已删除的顶点不出现在结果中
+pub fn toposort_kahn<G: Neighbors>(g: &G) -> Result<Vec<usize>, Cycle> {
     let mut in_degs = g.in_degs();
-    let mut q = VecDeque::new();
-    in_degs
-        .iter()
-        .enumerate()
-        .filter_map(|(v, &in_deg)| if in_deg == 0 { Some(v) } else { None })
-        .for_each(|v| q.push_back(v));
+    let mut q: VecDeque<usize> = g.vertices().filter(|&v| in_degs[v] == 0).collect();
     let mut topo = Vec::new();
     while let Some(u) = q.pop_front() {
         topo.push(u);
-        for &v in g.adj[u].iter() {
+        for v in g.neighbors(u) {
             in_degs[v] -= 1;
             if in_degs[v] == 0 {
                 q.push_back(v);
             }
         }
     }
-    topo
+    if topo.len() < g.vertices().count() {
+        let remaining: Vec<bool> = in_degs.iter().map(|&d| d > 0).collect();
+        return Err(find_cycle_kahn(g, &remaining));
+    }
+    Ok(topo)
+}
+
+// 按层输出, 同一层的顶点互不依赖, 可以并行执行, 层内升序
+pub fn toposort_levels(g: &Graph) -> Result<Vec<Vec<usize>>, Cycle> {
+    let mut in_degs = g.in_degs();
+    let mut level: Vec<usize> = g.vertices().filter(|&v| in_degs[v] == 0).collect();
+    let mut levels = Vec::new();
+    let mut cnt = 0;
+    while !level.is_empty() {
+        let mut next = Vec::new();
+        for &u in level.iter() {
+            for &v in g.adj[u].iter() {
+                in_degs[v] -= 1;
+                if in_degs[v] == 0 {
+                    next.push(v);
+                }
+            }
+        }
+        next.sort_unstable();
+        cnt += level.len();
+        levels.push(level);
+        level = next;
+    }
+    if cnt < g.vertices().count() {
+        let remaining: Vec<bool> = in_degs.iter().map(|&d| d > 0).collect();
+        return Err(find_cycle_kahn(g, &remaining));
+    }
+    Ok(levels)
+}
+
+// 字典序最小的拓扑序, 用小根堆代替队列
+pub fn toposort_lexical(g: &Graph) -> Result<Vec<usize>, Cycle> {
+    let mut in_degs = g.in_degs();
+    let mut heap: BinaryHeap<Reverse<usize>> = g
+        .vertices()
+        .filter(|&v| in_degs[v] == 0)
+        .map(Reverse)
+        .collect();
+    let mut topo = Vec::new();
+    while let Some(Reverse(u)) = heap.pop() {
+        topo.push(u);
+        for &v in g.adj[u].iter() {
+            in_degs[v] -= 1;
+            if in_degs[v] == 0 {
+                heap.push(Reverse(v));
+            }
+        }
+    }
+    if topo.len() < g.vertices().count() {
+        let remaining: Vec<bool> = in_degs.iter().map(|&d| d > 0).collect();
+        return Err(find_cycle_kahn(g, &remaining));
+    }
+    Ok(topo)
 }
 
-fn toposort_dfs_recur(topo: &mut Vec<usize>, g: &Graph, visited: &mut [i32], u: usize) -> bool {
-    visited[u] = 1;
-    for &v in g.adj[u].iter() {
-        if visited[v] == 1 {
-            return false;
+// 状压 dp, dp[s] 为以集合 s 为前缀的排列数, 只适用于小图
+pub fn count_toposorts(g: &Graph) -> u64 {
+    assert!(g.vs <= 20);
+    let mut preds = vec![0usize; g.vs];
+    for u in 0..g.vs {
+        for &v in g.adj[u].iter() {
+            preds[v] |= 1 << u;
         }
-        if visited[v] != 0 {
+    }
+    let mut dp = vec![0u64; 1 << g.vs];
+    // 已删除的顶点视为一开始就已排好
+    let removed = (0..g.vs)
+        .filter(|&v| g.removed[v])
+        .fold(0, |s, v| s | 1 << v);
+    dp[removed] = 1;
+    for s in 0..dp.len() {
+        if dp[s] == 0 {
             continue;
         }
-        if !toposort_dfs_recur(topo, g, visited, v) {
-            return false;
+        for (v, &p) in preds.iter().enumerate() {
+            if s & (1 << v) == 0 && p & s == p {
+                dp[s | (1 << v)] += dp[s];
+            }
         }
     }
-    visited[u] = 2;
-    topo.push(u);
-    true
+    dp[dp.len() - 1]
 }
 
-pub fn toposort_dfs(g: &Graph) -> Option<Vec<usize>> {
+fn all_toposorts_recur(
+    g: &Graph,
+    in_degs: &mut [usize],
+    used: &mut [bool],
+    topo: &mut Vec<usize>,
+    f: &mut impl FnMut(&[usize]),
+) {
+    if used.iter().all(|&u| u) {
+        f(topo);
+        return;
+    }
+    for u in 0..g.vs {
+        if used[u] || in_degs[u] != 0 {
+            continue;
+        }
+        used[u] = true;
+        topo.push(u);
+        g.adj[u].iter().for_each(|&v| in_degs[v] -= 1);
+        all_toposorts_recur(g, in_degs, used, topo, f);
+        g.adj[u].iter().for_each(|&v| in_degs[v] += 1);
+        topo.pop();
+        used[u] = false;
+    }
+}
+
+// 按字典序枚举全部拓扑序, 数量可能是阶乘级的, 只适用于小图
+pub fn all_toposorts(g: &Graph, mut f: impl FnMut(&[usize])) {
+    let mut in_degs = g.in_degs();
+    // 已删除的顶点视为已使用
+    let mut used = g.removed.clone();
+    let mut topo = Vec::with_capacity(g.vs);
+    all_toposorts_recur(g, &mut in_degs, &mut used, &mut topo, &mut f);
+}
+
+// 用显式栈模拟递归, 避免长链导致栈溢出
+fn toposort_dfs_iter(
+    topo: &mut Vec<usize>,
+    g: &Graph,
+    visited: &mut [i32],
+    root: usize,
+) -> Result<(), Cycle> {
+    let mut stack = vec![(root, 0)]; // 顶点, 下一个待访问的邻居下标
+    visited[root] = 1;
+    while let Some(top) = stack.last_mut() {
+        let u = top.0;
+        if top.1 < g.adj[u].len() {
+            let v = g.adj[u][top.1];
+            top.1 += 1;
+            if visited[v] == 1 {
+                // 栈中从 v 到 u 的顶点加上边 u -> v 构成环
+                let i = stack.iter().position(|&(w, _)| w == v).unwrap();
+                return Err(Cycle {
+                    vertices: stack[i..].iter().map(|&(w, _)| w).collect(),
+                    unordered: Vec::new(),
+                });
+            }
+            if visited[v] == 0 {
+                visited[v] = 1;
+                stack.push((v, 0));
+            }
+            continue;
+        }
+        stack.pop();
+        visited[u] = 2;
+        topo.push(u);
+    }
+    Ok(())
+}
+
+pub fn toposort_dfs(g: &Graph) -> Result<Vec<usize>, Cycle> {
     let mut topo = Vec::new();
     let mut visited = vec![0; g.vs];
-    for u in 0..g.vs {
+    for u in g.vertices() {
         if visited[u] != 0 {
             continue;
         }
-        if !toposort_dfs_recur(&mut topo, g, &mut visited, u) {
-            return None;
-        }
+        toposort_dfs_iter(&mut topo, g, &mut visited, u)?;
     }
     topo.reverse();
-    Some(topo)
+    Ok(topo)
 }
 
 #[cfg(test)]
 mod tests {
-    use crate::debug::set_debug;
-    use crate::graph::topo::toposort_dfs;
-    use crate::graph::Graph;
-
-    use super::toposort_kahn;
+    use super::*;
 
     fn get_g1() -> Graph {
         let mut g = Graph::new(5);
@@ -78,17 +260,163 @@ mod tests {
 
     #[test]
     fn test_toposort_kahn() {
-        set_debug(true);
         let g1 = get_g1();
         let topo = toposort_kahn(&g1);
-        D!(topo);
+        println!("topo = {:?}", topo);
+    }
+
+    // 递归版本, 只作为 toposort_dfs_iter 的参照; path 为递归栈上的顶点
+    fn toposort_dfs_recur(
+        topo: &mut Vec<usize>,
+        g: &Graph,
+        visited: &mut [i32],
+        path: &mut Vec<usize>,
+        u: usize,
+    ) -> Result<(), Cycle> {
+        visited[u] = 1;
+        path.push(u);
+        for &v in g.adj[u].iter() {
+            if visited[v] == 1 {
+                let i = path.iter().position(|&w| w == v).unwrap();
+                return Err(Cycle {
+                    vertices: path[i..].to_vec(),
+                    unordered: Vec::new(),
+                });
+            }
+            if visited[v] == 0 {
+                toposort_dfs_recur(topo, g, visited, path, v)?;
+            }
+        }
+        path.pop();
+        visited[u] = 2;
+        topo.push(u);
+        Ok(())
+    }
+
+    fn toposort_dfs_ref(g: &Graph) -> Result<Vec<usize>, Cycle> {
+        let mut topo = Vec::new();
+        let mut visited = vec![0; g.vs];
+        for u in g.vertices() {
+            if visited[u] == 0 {
+                toposort_dfs_recur(&mut topo, g, &mut visited, &mut Vec::new(), u)?;
+            }
+        }
+        topo.reverse();
+        Ok(topo)
     }
 
     #[test]
     fn test_toposort_dfs() {
-        set_debug(true);
         let g1 = get_g1();
         let topo = toposort_dfs(&g1);
-        D!(topo);
+        println!("topo = {:?}", topo);
+        assert_eq!(topo, toposort_dfs_ref(&g1));
+
+        for seed in 0..30 {
+            let n = 1 + seed as usize % 25;
+            let g = random::random_dag(n, 0.2, seed);
+            assert_eq!(toposort_dfs(&g), toposort_dfs_ref(&g));
+            let (g, _) = random::planted_cycle(n, 0.2, 1 + seed as usize % n, seed);
+            let c = toposort_dfs(&g);
+            assert!(c.is_err());
+            assert_eq!(c, toposort_dfs_ref(&g));
+            let g = random::erdos_renyi(n, 0.1, true, seed);
+            assert_eq!(toposort_dfs(&g), toposort_dfs_ref(&g));
+        }
+    }
+
+    fn check_cycle(g: &Graph, c: &Cycle) {
+        let n = c.vertices.len();
+        assert!(n > 0);
+        for i in 0..n {
+            let (u, v) = (c.vertices[i], c.vertices[(i + 1) % n]);
+            assert!(g.adj[u].contains(&v));
+        }
+    }
+
+    #[test]
+    fn test_cycle() {
+        let mut g2 = Graph::new(7);
+        g2.add_edge(2, 4);
+        g2.add_edge(0, 3);
+        g2.add_edge(0, 1);
+        g2.add_edge(3, 4);
+        g2.add_edge(1, 2);
+        g2.add_edge(3, 2);
+        g2.add_edge(1, 3);
+        g2.add_edge(4, 1);
+        g2.add_edge(4, 5);
+        g2.add_edge(5, 6);
+
+        let c = toposort_kahn(&g2).unwrap_err();
+        check_cycle(&g2, &c);
+        assert_eq!(c.unordered, vec![1, 2, 3, 4, 5, 6]);
+        println!("{}", c);
+
+        let c = toposort_dfs(&g2).unwrap_err();
+        check_cycle(&g2, &c);
+        assert_eq!(c.vertices, vec![4, 1, 2]);
+        assert_eq!(c.to_string(), "cycle detected: 4 -> 1 -> 2 -> 4");
+
+        let mut g3 = Graph::new(2);
+        g3.add_edge(0, 1);
+        g3.add_edge(1, 1);
+        let c = toposort_kahn(&g3).unwrap_err();
+        assert_eq!(c.vertices, vec![1]);
+        assert_eq!(c.unordered, vec![1]);
+        assert_eq!(toposort_dfs(&g3).unwrap_err().vertices, vec![1]);
+    }
+
+    #[test]
+    fn test_toposort_levels() {
+        let g1 = get_g1();
+        assert_eq!(
+            toposort_levels(&g1).unwrap(),
+            vec![vec![0], vec![1], vec![3], vec![2], vec![4]]
+        );
+        let mut g2 = Graph::new(6);
+        g2.add_edge(5, 0);
+        g2.add_edge(5, 2);
+        g2.add_edge(4, 0);
+        g2.add_edge(4, 1);
+        g2.add_edge(2, 3);
+        g2.add_edge(3, 1);
+        assert_eq!(
+            toposort_levels(&g2).unwrap(),
+            vec![vec![4, 5], vec![0, 2], vec![3], vec![1]]
+        );
+        assert_eq!(toposort_lexical(&g2).unwrap(), vec![4, 5, 0, 2, 3, 1]);
+        g2.add_edge(1, 5);
+        assert_eq!(
+            toposort_levels(&g2).unwrap_err().unordered,
+            vec![0, 1, 2, 3, 5]
+        );
+        assert!(toposort_lexical(&g2).is_err());
+    }
+
+    #[test]
+    fn test_all_toposorts() {
+        let g1 = get_g1();
+        assert_eq!(count_toposorts(&g1), 1);
+        assert_eq!(count_toposorts(&Graph::new(4)), 24);
+
+        let mut g2 = Graph::new(6);
+        g2.add_edge(5, 0);
+        g2.add_edge(5, 2);
+        g2.add_edge(4, 0);
+        g2.add_edge(4, 1);
+        g2.add_edge(2, 3);
+        g2.add_edge(3, 1);
+        let mut orders = Vec::new();
+        all_toposorts(&g2, |topo| orders.push(topo.to_vec()));
+        assert_eq!(orders.len() as u64, count_toposorts(&g2));
+        assert_eq!(orders[0], toposort_lexical(&g2).unwrap());
+        assert!(orders.windows(2).all(|w| w[0] < w[1]));
+
+        g2.add_edge(1, 5);
+        assert_eq!(count_toposorts(&g2), 0);
+        let mut cnt = 0;
+        all_toposorts(&g2, |_| cnt += 1);
+        assert_eq!(cnt, 0);
     }
 }
@@ -0,0 +1,301 @@
+// https://oi-wiki.org/graph/lca/
+// 有根树视图, 边可以只从父亲指向孩子, 也可以双向存储
+
+use std::collections::VecDeque;
+
+use super::*;
+
+pub struct Tree {
+    root: usize,
+    parent: Vec<usize>, // 根的父亲为自身
+    children: Vec<Vec<usize>>,
+    depth: Vec<usize>,
+    size: Vec<usize>,
+    up: Vec<Vec<usize>>,     // up[k][v] 为 v 的第 2^k 个祖先, 超出时为根
+    tour: Vec<usize>,        // 欧拉序, 每进入或回到一个顶点记录一次, 长度 2n - 1
+    first: Vec<usize>,       // 顶点在 tour 中第一次出现的位置
+    sparse: Vec<Vec<usize>>, // sparse[k][i] 为 tour[i..i + 2^k] 中深度最小的顶点
+}
+
+impl Tree {
+    // 有未删除的顶点不可达或存在环时返回 None
+    pub fn new(g: &Graph, root: usize) -> Option<Self> {
+        let n = g.vs;
+        let mut parent = vec![usize::MAX; n];
+        let mut children = vec![Vec::new(); n];
+        let mut depth = vec![0; n];
+        let mut order = Vec::with_capacity(n);
+        let mut q = VecDeque::new();
+        parent[root] = root;
+        q.push_back(root);
+        while let Some(v) = q.pop_front() {
+            order.push(v);
+            let mut skip_parent = v != root;
+            for &w in g.adj[v].iter() {
+                // 双向存储时跳过一次指回父亲的边
+                if skip_parent && w == parent[v] {
+                    skip_parent = false;
+                    continue;
+                }
+                if parent[w] != usize::MAX {
+                    return None;
+                }
+                parent[w] = v;
+                depth[w] = depth[v] + 1;
+                children[v].push(w);
+                q.push_back(w);
+            }
+        }
+        if order.len() != g.vertices().count() {
+            return None;
+        }
+
+        let mut size = vec![1; n];
+        for &v in order.iter().rev() {
+            if v != root {
+                size[parent[v]] += size[v];
+            }
+        }
+
+        let mut up = vec![parent.clone()];
+        while 1 << up.len() < n {
+            let last = up.last().unwrap();
+            let next = (0..n).map(|v| last[last[v]]).collect();
+            up.push(next);
+        }
+
+        let mut tour = Vec::with_capacity(2 * n);
+        let mut first = vec![0; n];
+        let mut stack = vec![(root, 0)]; // 顶点, 下一个待访问的孩子下标
+        first[root] = 0;
+        tour.push(root);
+        while let Some(top) = stack.last_mut() {
+            let v = top.0;
+            if top.1 == children[v].len() {
+                stack.pop();
+                if let Some(&(p, _)) = stack.last() {
+                    tour.push(p);
+                }
+                continue;
+            }
+            let w = children[v][top.1];
+            top.1 += 1;
+            first[w] = tour.len();
+            tour.push(w);
+            stack.push((w, 0));
+        }
+
+        let mut sparse = vec![tour.clone()];
+        let mut k = 1;
+        while 1 << k <= tour.len() {
+            let last = sparse.last().unwrap();
+            let half = 1 << (k - 1);
+            let next = (0..=tour.len() - (1 << k))
+                .map(|i| {
+                    let (a, b) = (last[i], last[i + half]);
+                    if depth[a] <= depth[b] {
+                        a
+                    } else {
+                        b
+                    }
+                })
+                .collect();
+            sparse.push(next);
+            k += 1;
+        }
+
+        Some(Self {
+            root,
+            parent,
+            children,
+            depth,
+            size,
+            up,
+            tour,
+            first,
+            sparse,
+        })
+    }
+
+    pub fn root(&self) -> usize {
+        self.root
+    }
+
+    pub fn parent(&self, v: usize) -> Option<usize> {
+        if v == self.root {
+            None
+        } else {
+            Some(self.parent[v])
+        }
+    }
+
+    pub fn children(&self, v: usize) -> &[usize] {
+        &self.children[v]
+    }
+
+    pub fn depth(&self, v: usize) -> usize {
+        self.depth[v]
+    }
+
+    pub fn subtree_size(&self, v: usize) -> usize {
+        self.size[v]
+    }
+
+    pub fn euler_tour(&self) -> &[usize] {
+        &self.tour
+    }
+
+    // k 超过深度时返回 None
+    pub fn kth_ancestor(&self, v: usize, k: usize) -> Option<usize> {
+        if k > self.depth[v] {
+            return None;
+        }
+        let mut v = v;
+        for (i, up) in self.up.iter().enumerate() {
+            if k >> i & 1 == 1 {
+                v = up[v];
+            }
+        }
+        Some(v)
+    }
+
+    // 倍增: 先跳到同一深度, 再从高到低一起跳
+    pub fn lca(&self, u: usize, v: usize) -> usize {
+        let (mut u, mut v) = if self.depth[u] < self.depth[v] {
+            (v, u)
+        } else {
+            (u, v)
+        };
+        u = self.kth_ancestor(u, self.depth[u] - self.depth[v]).unwrap();
+        if u == v {
+            return u;
+        }
+        for up in self.up.iter().rev() {
+            if up[u] != up[v] {
+                u = up[u];
+                v = up[v];
+            }
+        }
+        self.parent[u]
+    }
+
+    // 欧拉序上 first[u]..=first[v] 区间内深度最小的顶点, O(1) 查询
+    pub fn lca_euler(&self, u: usize, v: usize) -> usize {
+        let (l, r) = {
+            let (a, b) = (self.first[u], self.first[v]);
+            (a.min(b), a.max(b) + 1)
+        };
+        let k = (usize::BITS - 1 - (r - l).leading_zeros()) as usize;
+        let (a, b) = (self.sparse[k][l], self.sparse[k][r - (1 << k)]);
+        if self.depth[a] <= self.depth[b] {
+            a
+        } else {
+            b
+        }
+    }
+
+    pub fn dist(&self, u: usize, v: usize) -> usize {
+        self.depth[u] + self.depth[v] - 2 * self.depth[self.lca(u, v)]
+    }
+
+    fn farthest(&self, s: usize) -> (usize, Vec<usize>) {
+        let n = self.parent.len();
+        let mut from = vec![usize::MAX; n];
+        let mut q = VecDeque::new();
+        from[s] = s;
+        q.push_back(s);
+        let mut last = s;
+        while let Some(v) = q.pop_front() {
+            last = v;
+            let up = self.parent(v).into_iter();
+            for w in up.chain(self.children[v].iter().copied()) {
+                if from[w] == usize::MAX {
+                    from[w] = v;
+                    q.push_back(w);
+                }
+            }
+        }
+        (last, from)
+    }
+
+    // 两次 bfs, 返回最长路径上的顶点序列
+    pub fn diameter(&self) -> Vec<usize> {
+        let (a, _) = self.farthest(self.root);
+        let (b, from) = self.farthest(a);
+        let mut path = vec![b];
+        let mut v = b;
+        while v != a {
+            v = from[v];
+            path.push(v);
+        }
+        path.reverse();
+        path
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    //         0
+    //       / | \
+    //      1  2  3
+    //     / \     \
+    //    4   5     6
+    //        |
+    //        7
+    fn get_tree() -> Graph {
+        let mut g = Graph::new(8);
+        for &(p, c) in [(0, 1), (0, 2), (0, 3), (1, 4), (1, 5), (3, 6), (5, 7)].iter() {
+            g.add_edge(p, c);
+        }
+        g
+    }
+
+    #[test]
+    fn test_tree() {
+        let t = Tree::new(&get_tree(), 0).unwrap();
+        assert_eq!(t.parent(0), None);
+        assert_eq!(t.parent(7), Some(5));
+        assert_eq!(t.children(1), &[4, 5]);
+        assert_eq!(t.depth(7), 3);
+        assert_eq!(t.subtree_size(1), 4);
+        assert_eq!(t.subtree_size(0), 8);
+        assert_eq!(
+            t.euler_tour(),
+            &[0, 1, 4, 1, 5, 7, 5, 1, 0, 2, 0, 3, 6, 3, 0]
+        );
+        assert_eq!(t.kth_ancestor(7, 2), Some(1));
+        assert_eq!(t.kth_ancestor(7, 3), Some(0));
+        assert_eq!(t.kth_ancestor(7, 4), None);
+        for u in 0..8 {
+            for v in 0..8 {
+                assert_eq!(t.lca(u, v), t.lca_euler(u, v));
+            }
+        }
+        assert_eq!(t.lca(4, 7), 1);
+        assert_eq!(t.lca(7, 6), 0);
+        assert_eq!(t.lca(5, 7), 5);
+        assert_eq!(t.dist(7, 6), 5);
+        assert_eq!(t.diameter(), vec![7, 5, 1, 0, 3, 6]);
+
+        // 双向存储, 以 5 为根
+        let g = get_tree();
+        let mut un = UnGraph::new(8);
+        for v in 0..8 {
+            for &w in g.adj[v].iter() {
+                un.add_edge(v, w);
+            }
+        }
+        let t = Tree::new(&un.to_graph(), 5).unwrap();
+        assert_eq!(t.parent(0), Some(1));
+        assert_eq!(t.subtree_size(1), 6);
+        assert_eq!(t.lca(4, 6), 1);
+        assert_eq!(t.diameter().len(), 6);
+
+        let mut g = get_tree();
+        g.add_edge(6, 2);
+        assert!(Tree::new(&g, 0).is_none());
+        assert!(Tree::new(&get_tree(), 1).is_none());
+    }
+}
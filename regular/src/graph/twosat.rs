@@ -0,0 +1,150 @@
+// https://oi-wiki.org/graph/2-sat/
+// 变量 x 为真对应顶点 2x, 为假对应 2x + 1; 子句 a ∨ b 连边 ¬a -> b 与 ¬b -> a
+
+use super::sc::tarjan_scc;
+use super::*;
+
+pub struct TwoSat {
+    vars: usize,
+    edges: Vec<(usize, usize)>,
+}
+
+// 字面量 (x, neg) 为 neg 时表示 ¬x
+fn node(x: usize, neg: bool) -> usize {
+    2 * x + neg as usize
+}
+
+impl TwoSat {
+    pub fn new(vars: usize) -> Self {
+        Self {
+            vars,
+            edges: Vec::new(),
+        }
+    }
+
+    pub fn new_var(&mut self) -> usize {
+        self.vars += 1;
+        self.vars - 1
+    }
+
+    // (neg_a ? ¬a : a) ∨ (neg_b ? ¬b : b)
+    pub fn add_clause(&mut self, a: usize, neg_a: bool, b: usize, neg_b: bool) {
+        self.edges.push((node(a, !neg_a), node(b, neg_b)));
+        self.edges.push((node(b, !neg_b), node(a, neg_a)));
+    }
+
+    // 强制字面量为真
+    pub fn set(&mut self, a: usize, neg_a: bool) {
+        self.add_clause(a, neg_a, a, neg_a);
+    }
+
+    // a -> b 等价于 ¬a ∨ b
+    pub fn implies(&mut self, a: usize, neg_a: bool, b: usize, neg_b: bool) {
+        self.add_clause(a, !neg_a, b, neg_b);
+    }
+
+    // a 与 b 恰有一个为真
+    pub fn xor(&mut self, a: usize, neg_a: bool, b: usize, neg_b: bool) {
+        self.add_clause(a, neg_a, b, neg_b);
+        self.add_clause(a, !neg_a, b, !neg_b);
+    }
+
+    // 前缀编码, 每个字面量引入一个辅助变量 p_i = l_0 ∨ ... ∨ l_i, 子句数为线性
+    pub fn at_most_one(&mut self, lits: &[(usize, bool)]) {
+        if lits.len() <= 1 {
+            return;
+        }
+        let mut prev: Option<usize> = None;
+        for &(x, neg) in lits.iter() {
+            let p = self.new_var();
+            self.implies(x, neg, p, false);
+            if let Some(q) = prev {
+                self.implies(q, false, p, false);
+                self.implies(q, false, x, !neg);
+            }
+            prev = Some(p);
+        }
+    }
+
+    // 不可满足时返回 None; 辅助变量的取值也在结果中
+    pub fn solve(&self) -> Option<Vec<bool>> {
+        let mut g = Graph::new(2 * self.vars);
+        for &(u, v) in self.edges.iter() {
+            g.add_edge(u, v);
+        }
+        // tarjan_scc 按逆拓扑序给出分量, 取拓扑序靠后的一侧
+        let mut comp = vec![0; g.vs];
+        for (i, scc) in tarjan_scc(&g).iter().enumerate() {
+            for &v in scc.iter() {
+                comp[v] = i;
+            }
+        }
+        (0..self.vars)
+            .map(|x| {
+                let (t, f) = (comp[node(x, false)], comp[node(x, true)]);
+                if t == f {
+                    None
+                } else {
+                    Some(t < f)
+                }
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lit(assign: &[bool], x: usize, neg: bool) -> bool {
+        assign[x] != neg
+    }
+
+    #[test]
+    fn test_two_sat() {
+        // (x0 ∨ ¬x1) ∧ (x1 ∨ x2) ∧ (¬x0 ∨ ¬x2) ∧ (x0 -> x1)
+        let mut s = TwoSat::new(3);
+        s.add_clause(0, false, 1, true);
+        s.add_clause(1, false, 2, false);
+        s.add_clause(0, true, 2, true);
+        s.implies(0, false, 1, false);
+        let a = s.solve().unwrap();
+        assert!(lit(&a, 0, false) || lit(&a, 1, true));
+        assert!(lit(&a, 1, false) || lit(&a, 2, false));
+        assert!(lit(&a, 0, true) || lit(&a, 2, true));
+        assert!(!a[0] || a[1]);
+
+        s.set(2, false);
+        s.set(0, false);
+        assert_eq!(s.solve(), None);
+
+        let mut s = TwoSat::new(2);
+        s.xor(0, false, 1, false);
+        s.set(1, true);
+        assert_eq!(s.solve(), Some(vec![true, false]));
+        s.set(0, true);
+        assert_eq!(s.solve(), None);
+    }
+
+    #[test]
+    fn test_at_most_one() {
+        let n = 5;
+        for forced in 0..n {
+            let mut s = TwoSat::new(n);
+            let lits: Vec<(usize, bool)> = (0..n).map(|x| (x, false)).collect();
+            s.at_most_one(&lits);
+            s.set(forced, false);
+            let a = s.solve().unwrap();
+            assert_eq!(a[..n].iter().filter(|&&b| b).count(), 1);
+            assert!(a[forced]);
+            s.set((forced + 1) % n, false);
+            assert_eq!(s.solve(), None);
+        }
+        // 带取反的字面量: 至多一个为假
+        let mut s = TwoSat::new(3);
+        s.at_most_one(&[(0, true), (1, true), (2, true)]);
+        s.set(0, true);
+        let a = s.solve().unwrap();
+        assert_eq!(a[..3], [false, true, true]);
+    }
+}
@@ -1,6 +1,7 @@
 pub mod bfprt;
 pub mod btree;
 pub mod datrie;
+pub mod dsu;
 pub mod graph;
 pub mod rand;
 pub mod ring;
@@ -7,11 +7,11 @@ pub struct PseudoRand {
 }
 
 impl PseudoRand {
-    fn new(seed: u64) -> Self {
+    pub fn new(seed: u64) -> Self {
         Self { s: seed }
     }
 
-    fn rand(&mut self) -> u64 {
+    pub fn rand(&mut self) -> u64 {
         let x = self.s.wrapping_mul(1103515245).wrapping_add(12345);
         self.s = x;
         x